@@ -1,5 +1,8 @@
 use crate::prelude::*;
 
+mod expiry;
+pub use expiry::*;
+
 #[serde_as]
 #[skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Deserialize, TypedBuilder)]
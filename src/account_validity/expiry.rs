@@ -0,0 +1,129 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use crate::{
+    prelude::*,
+    server_notices::{BroadcastReport, NoticeTemplate, NoticeVariables},
+    SynapseError,
+};
+
+use super::UpdateAccountValidityBody;
+
+/// When a user's account expires.
+///
+/// Synapse's admin API can't report expiration times, so these come from your own records (or
+/// Synapse's `account_validity` table), or from the results of `extend_account_validity`.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountExpiry {
+    pub user_id: OwnedUserId,
+    #[serde_as(as = "TimestampMilliSeconds<i64>")]
+    pub expiration_ts: SystemTime,
+}
+
+impl AccountExpiry {
+    /// Whether the account expires within `within` from now. Accounts which have already
+    /// expired are included.
+    pub fn expires_within(&self, within: Duration) -> bool {
+        SystemTime::now()
+            .checked_add(within)
+            .is_none_or(|deadline| self.expiration_ts <= deadline)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ValidityExtension {
+    /// Set the accounts to expire at this time.
+    Until(SystemTime),
+    /// Set the accounts to expire this long from now. Expiration times past the end of the year
+    /// 9999 are clamped to it, so `For(Duration::MAX)` keeps accounts from ever expiring.
+    For(Duration),
+}
+
+impl ValidityExtension {
+    /// 9999-12-31T23:59:59Z
+    const LATEST_EXPIRATION_SECS: u64 = 253_402_300_799;
+
+    fn expiration_ts(self, now: SystemTime) -> SystemTime {
+        match self {
+            ValidityExtension::Until(ts) => ts,
+            ValidityExtension::For(duration) => {
+                let latest = UNIX_EPOCH + Duration::from_secs(Self::LATEST_EXPIRATION_SECS);
+                now.checked_add(duration)
+                    .map_or(latest, |expiration_ts| expiration_ts.min(latest))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct AccountValidityReport {
+    /// The new expiration time of each updated account.
+    pub updated: Vec<AccountExpiry>,
+    pub failed: Vec<(OwnedUserId, SynapseError)>,
+}
+
+impl SynapseClient {
+    /// Send a renewal warning to each of `accounts` which expires within `within`.
+    ///
+    /// The `expiry_date` variable of `template` is set to the account's expiration date, see
+    /// `send_templated_notice`.
+    ///
+    /// ```rs
+    /// let template = NoticeTemplate::new(
+    ///     "en",
+    ///     "Your account expires on **{expiry_date}**, check your email to renew it.",
+    /// );
+    /// let report = client
+    ///     .warn_expiring_accounts(&accounts, Duration::from_secs(7 * 24 * 60 * 60), &template, |_| None)
+    ///     .await;
+    /// ```
+    pub async fn warn_expiring_accounts(
+        &self,
+        accounts: &[AccountExpiry],
+        within: Duration,
+        template: &NoticeTemplate,
+        locale: impl Fn(&UserId) -> Option<String>,
+    ) -> BroadcastReport {
+        let mut report = BroadcastReport::default();
+        for account in accounts
+            .iter()
+            .filter(|account| account.expires_within(within))
+        {
+            let vars = NoticeVariables::new().date("expiry_date", account.expiration_ts);
+            match self
+                .send_templated_notice(&account.user_id, template, &vars, &locale)
+                .await
+            {
+                Ok(event_id) => report.sent.push((account.user_id.clone(), event_id)),
+                Err(err) => report.failed.push((account.user_id.clone(), err)),
+            }
+        }
+        report
+    }
+
+    /// Set the expiration time of every account in `user_ids`, e.g. to keep staff accounts
+    /// from expiring.
+    pub async fn extend_account_validity(
+        &self,
+        user_ids: impl IntoIterator<Item = OwnedUserId>,
+        extension: ValidityExtension,
+    ) -> AccountValidityReport {
+        let expiration_ts = extension.expiration_ts(SystemTime::now());
+
+        let mut report = AccountValidityReport::default();
+        for user_id in user_ids {
+            let body = UpdateAccountValidityBody::builder()
+                .user_id(user_id.clone())
+                .expiration_ts(expiration_ts)
+                .build();
+            match self.update_account_validity(body).await {
+                Ok(expiration_ts) => report.updated.push(AccountExpiry {
+                    user_id,
+                    expiration_ts,
+                }),
+                Err(err) => report.failed.push((user_id, err)),
+            }
+        }
+        report
+    }
+}
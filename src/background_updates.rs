@@ -2,7 +2,6 @@ mod get;
 pub use get::*;
 
 mod enabled;
-pub use enabled::*;
 
 mod run;
 pub use run::*;
@@ -0,0 +1,62 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub(crate) const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The number of whole days between the Unix epoch and `ts`, or 0 if `ts` is before the epoch.
+pub(crate) fn days_since_epoch(ts: SystemTime) -> u64 {
+    ts.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() / DAY.as_secs()
+}
+
+/// Midnight (UTC) at the start of the day containing `ts`.
+pub(crate) fn start_of_day(ts: SystemTime) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(days_since_epoch(ts) * DAY.as_secs())
+}
+
+/// Format a timestamp as a `YYYY-MM-DD` date in UTC.
+pub(crate) fn format_date(ts: SystemTime) -> String {
+    // Howard Hinnant's `civil_from_days`, see http://howardhinnant.github.io/date_algorithms.html
+    let days = days_since_epoch(ts) as i64;
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn formats_the_epoch() {
+        assert_eq!(format_date(UNIX_EPOCH), "1970-01-01");
+        assert_eq!(format_date(at(DAY.as_secs() - 1)), "1970-01-01");
+    }
+
+    #[test]
+    fn formats_leap_days() {
+        assert_eq!(format_date(at(1709164800)), "2024-02-29");
+        assert_eq!(format_date(at(1709164800 + DAY.as_secs())), "2024-03-01");
+        assert_eq!(format_date(at(951782400)), "2000-02-29");
+    }
+
+    #[test]
+    fn formats_the_end_of_a_year() {
+        assert_eq!(format_date(at(1703980800 + 12 * 60 * 60)), "2023-12-31");
+    }
+
+    #[test]
+    fn truncates_to_the_start_of_the_day() {
+        assert_eq!(start_of_day(at(1709164800 + 123)), at(1709164800));
+        assert_eq!(start_of_day(at(1709164800)), at(1709164800));
+    }
+}
@@ -3,3 +3,11 @@ pub use get_all::*;
 
 mod get;
 pub use get::*;
+
+mod delete;
+
+mod triage;
+pub use triage::*;
+
+mod summary;
+pub use summary::*;
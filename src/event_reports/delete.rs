@@ -0,0 +1,16 @@
+use crate::prelude::*;
+
+impl SynapseClient {
+    /// Delete an event report, e.g. once it has been dealt with.
+    pub async fn delete_event_report(&self, report_id: i32) -> Result<()> {
+        execute!(
+            self.inner
+                .delete(endpoint!(self format!("/event_reports/{report_id}")))
+                .send()
+                .await?
+                .json::<MatrixResult<EmptyObject>>()
+                .await?;
+            _r => ()
+        )
+    }
+}
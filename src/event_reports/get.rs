@@ -1,3 +1,8 @@
+use matrix_sdk::ruma::{
+    events::{room::message::RoomMessageEventContent, AnyTimelineEvent},
+    serde::Raw,
+};
+
 use crate::prelude::*;
 
 #[serde_as]
@@ -5,9 +10,9 @@ use crate::prelude::*;
 pub struct SingleEventReport {
     /// The ID of the event that was reported.
     pub event_id: OwnedEventId,
-    // TODO: figure out if ruma can deserialize this into the appropriate type
-    /// The event that was reported.
-    pub event_json: serde_json::Value,
+    /// The event that was reported, in the format sent between servers. Use `event` to
+    /// deserialize it.
+    pub event_json: Raw<AnyTimelineEvent>,
     /// The ID of the report.
     pub id: i32,
     /// The user specified reason for the report.
@@ -29,6 +34,63 @@ pub struct SingleEventReport {
     pub user_id: OwnedUserId,
 }
 
+impl SingleEventReport {
+    pub fn event(&self) -> serde_json::Result<AnyTimelineEvent> {
+        // events sent between servers don't include their ID in room versions 3 and up
+        let mut event: serde_json::Map<String, serde_json::Value> =
+            self.event_json.deserialize_as()?;
+        event
+            .entry("event_id")
+            .or_insert_with(|| self.event_id.as_str().into());
+        serde_json::from_value(event.into())
+    }
+
+    fn message_content(&self) -> Option<RoomMessageEventContent> {
+        let event_type = self.event_json.get_field::<String>("type").ok()??;
+        if event_type != "m.room.message" {
+            return None;
+        }
+        self.event_json.get_field("content").ok()?
+    }
+
+    /// The body of the reported message, if the event is an unredacted `m.room.message`.
+    pub fn body(&self) -> Option<String> {
+        self.message_content()
+            .map(|content| content.body().to_owned())
+    }
+
+    /// The `msgtype` of the reported message, if the event is an unredacted `m.room.message`.
+    pub fn msgtype(&self) -> Option<String> {
+        self.message_content()
+            .map(|content| content.msgtype().to_owned())
+    }
+
+    /// Every `mxc://` URI referenced in the content of the reported event, such as attachments,
+    /// thumbnails, stickers and avatars.
+    pub fn media_urls(&self) -> Vec<OwnedMxcUri> {
+        let mut mxcs = Vec::new();
+        if let Ok(Some(content)) = self.event_json.get_field::<serde_json::Value>("content") {
+            collect_mxc_uris(&content, &mut mxcs);
+        }
+        mxcs
+    }
+}
+
+fn collect_mxc_uris(value: &serde_json::Value, mxcs: &mut Vec<OwnedMxcUri>) {
+    match value {
+        serde_json::Value::String(s) if s.starts_with("mxc://") => {
+            mxcs.push(OwnedMxcUri::from(s.as_str()))
+        }
+        serde_json::Value::Array(values) => values
+            .iter()
+            .for_each(|value| collect_mxc_uris(value, mxcs)),
+        serde_json::Value::Object(map) => {
+            map.values().for_each(|value| collect_mxc_uris(value, mxcs))
+        }
+        _ => {}
+    }
+}
+
 impl SynapseClient {
     pub async fn get_event_report(&self, report_id: i32) -> Result<SingleEventReport> {
         execute!(
@@ -41,3 +103,95 @@ impl SynapseClient {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // from https://matrix-org.github.io/synapse/latest/admin_api/event_reports.html
+    fn report(content: serde_json::Value) -> SingleEventReport {
+        serde_json::from_value(serde_json::json!({
+            "event_id": "$M_cS06BY2NgaSNANlt1P6ZnhubdS_Ud9BGxCFAtAPU",
+            "event_json": {
+                "auth_events": [
+                    "$YK4arsKKcc0LRoe700pS8DSjOvUT4NDv0HfInlMFw2M",
+                    "$oggsNXxzPFRE3y53SUNd7nsj69-QzKv03a1RucHu-ws"
+                ],
+                "content": content,
+                "depth": 546,
+                "hashes": {
+                    "sha256": "xK1//xnmvHJIOvbgXlkI8eEqdvoMmihVDJ9J4SNlsAY"
+                },
+                "origin": "matrix.org",
+                "origin_server_ts": 1592291711430u64,
+                "prev_events": ["$YK4arsKKcc0LRoe700pS8DSjOvUT4NDv0HfInlMFw2M"],
+                "prev_state": [],
+                "room_id": "!ERAgBpSOcCCuTJqQPk:matrix.org",
+                "sender": "@foobar:matrix.org",
+                "signatures": {
+                    "matrix.org": {
+                        "ed25519:a_JaEG": "cs+OUKW/iHx5pEidbWxh0UiNNHwe46Ei9g7aL+l0nNRNm20TCNXUJtInw6UI3unKBZhzn1XkhHxVX+iHrqYI4v"
+                    }
+                },
+                "type": "m.room.message",
+                "unsigned": {
+                    "age_ts": 1592291711430u64
+                }
+            },
+            "id": 2,
+            "reason": "foo",
+            "score": -100,
+            "received_ts": 1570897107409u64,
+            "canonical_alias": "#alias1:matrix.org",
+            "room_id": "!ERAgBpSOcCCuTJqQPk:matrix.org",
+            "name": "Matrix HQ",
+            "sender": "@foobar:matrix.org",
+            "user_id": "@foo:matrix.org"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn reads_documented_example() {
+        let report = report(serde_json::json!({
+            "body": "matrix.org: This Week in Matrix",
+            "format": "org.matrix.custom.html",
+            "formatted_body": "<strong>matrix.org</strong>:<br><a href=\"https://matrix.org/blog/\"><strong>This Week in Matrix</strong></a>",
+            "msgtype": "m.notice"
+        }));
+
+        let event = report.event().unwrap();
+        assert_eq!(event.event_id(), report.event_id);
+        assert_eq!(
+            report.body().as_deref(),
+            Some("matrix.org: This Week in Matrix")
+        );
+        assert_eq!(report.msgtype().as_deref(), Some("m.notice"));
+        assert!(report.media_urls().is_empty());
+    }
+
+    #[test]
+    fn finds_media_in_images() {
+        let report = report(serde_json::json!({
+            "body": "cat.png",
+            "msgtype": "m.image",
+            "url": "mxc://matrix.org/cat",
+            "info": {
+                "mimetype": "image/png",
+                "thumbnail_url": "mxc://matrix.org/cat_thumb"
+            }
+        }));
+
+        assert_eq!(report.body().as_deref(), Some("cat.png"));
+        assert_eq!(report.msgtype().as_deref(), Some("m.image"));
+        let mut media_urls = report.media_urls();
+        media_urls.sort();
+        assert_eq!(
+            media_urls,
+            [
+                OwnedMxcUri::from("mxc://matrix.org/cat"),
+                OwnedMxcUri::from("mxc://matrix.org/cat_thumb")
+            ]
+        );
+    }
+}
@@ -1,4 +1,6 @@
-use crate::prelude::*;
+use futures_util::{future, Stream, TryStreamExt};
+
+use crate::{pagination::paginate, prelude::*};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EventReports {
@@ -34,18 +36,23 @@ pub struct EventReport {
 }
 
 #[skip_serializing_none]
-#[derive(Debug, Serialize, TypedBuilder)]
+#[derive(Debug, Clone, Serialize, TypedBuilder)]
 pub struct GetEventReportsQuery<'a> {
     #[builder(default, setter(strip_option))]
-    limit: Option<usize>,
+    pub limit: Option<usize>,
+    #[builder(default, setter(strip_option))]
+    pub from: Option<i32>,
+    /// Defaults to newest first.
     #[builder(default, setter(strip_option))]
-    from: Option<i32>,
+    pub dir: Option<SortDirection>,
+    /// Only return reports made by this user.
     #[builder(default, setter(strip_option))]
-    dir: Option<SortDirection>,
+    pub user_id: Option<&'a UserId>,
     #[builder(default, setter(strip_option))]
-    user_id: Option<&'a UserId>,
+    pub room_id: Option<&'a RoomId>,
+    /// Only return reports of events sent by this user.
     #[builder(default, setter(strip_option))]
-    room_id: Option<&'a RoomId>,
+    pub event_sender_user_id: Option<&'a UserId>,
 }
 
 impl SynapseClient {
@@ -60,4 +67,44 @@ impl SynapseClient {
                 .await?
         )
     }
+
+    /// Stream every event report matching `query`, following `next_token` until the last page.
+    pub fn stream_event_reports<'a>(
+        &'a self,
+        query: GetEventReportsQuery<'a>,
+    ) -> impl Stream<Item = Result<EventReport>> + 'a {
+        paginate(query, move |query: GetEventReportsQuery| async move {
+            let page = self.get_event_reports(query.clone()).await?;
+            let next = page.next_token.map(|from| GetEventReportsQuery {
+                from: Some(from),
+                ..query
+            });
+            Ok((page.event_reports, next))
+        })
+    }
+
+    /// Stream the event reports matching `query` which were received within `window`.
+    ///
+    /// Synapse can't filter reports by time, but returns them in order, so this stops fetching
+    /// pages once it has passed the end of the window.
+    pub fn stream_event_reports_between<'a>(
+        &'a self,
+        query: GetEventReportsQuery<'a>,
+        window: TimeWindow,
+    ) -> impl Stream<Item = Result<EventReport>> + 'a {
+        let newest_first = !matches!(query.dir, Some(SortDirection::Ascending));
+        self.stream_event_reports(query)
+            .try_take_while(move |report| {
+                future::ready(Ok(if newest_first {
+                    report.received_ts >= window.from
+                } else {
+                    report.received_ts <= window.until
+                }))
+            })
+            .try_filter(move |report| {
+                future::ready(
+                    report.received_ts >= window.from && report.received_ts <= window.until,
+                )
+            })
+    }
 }
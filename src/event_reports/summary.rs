@@ -0,0 +1,52 @@
+use std::{cmp::Reverse, collections::BTreeMap};
+
+use crate::{dates::start_of_day, prelude::*};
+
+use super::EventReport;
+
+/// Counts of event reports, to help spot campaigns where many reports target the same room or
+/// sender in a short time.
+#[serde_as]
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EventReportSummary {
+    pub total: usize,
+    pub per_room: BTreeMap<OwnedRoomId, usize>,
+    /// Keyed by the sender of the reported event.
+    pub per_sender: BTreeMap<OwnedUserId, usize>,
+    /// Keyed by the start of each day (UTC) on which reports were received.
+    #[serde_as(as = "BTreeMap<TimestampMilliSeconds<i64>, _>")]
+    pub per_day: BTreeMap<SystemTime, usize>,
+}
+
+impl EventReportSummary {
+    pub fn new<'a>(reports: impl IntoIterator<Item = &'a EventReport>) -> Self {
+        let mut summary = Self::default();
+        for report in reports {
+            summary.total += 1;
+            *summary.per_room.entry(report.room_id.clone()).or_default() += 1;
+            *summary.per_sender.entry(report.sender.clone()).or_default() += 1;
+            *summary
+                .per_day
+                .entry(start_of_day(report.received_ts))
+                .or_default() += 1;
+        }
+        summary
+    }
+
+    /// The `n` most reported rooms, most reports first.
+    pub fn top_rooms(&self, n: usize) -> Vec<(&OwnedRoomId, usize)> {
+        top(&self.per_room, n)
+    }
+
+    /// The `n` most reported senders, most reports first.
+    pub fn top_senders(&self, n: usize) -> Vec<(&OwnedUserId, usize)> {
+        top(&self.per_sender, n)
+    }
+}
+
+fn top<K>(counts: &BTreeMap<K, usize>, n: usize) -> Vec<(&K, usize)> {
+    let mut counts: Vec<_> = counts.iter().map(|(key, &count)| (key, count)).collect();
+    counts.sort_by_key(|&(_, count)| Reverse(count));
+    counts.truncate(n);
+    counts
+}
@@ -0,0 +1,117 @@
+use std::collections::BTreeMap;
+
+use futures_util::TryStreamExt;
+
+use crate::{prelude::*, rooms::EventContext};
+
+use super::{EventReport, GetEventReportsQuery, SingleEventReport};
+
+/// Event reports grouped by the sender of the reported event and by room, to make it easier to
+/// spot users or rooms which are reported repeatedly.
+#[derive(Debug, Clone, Default)]
+pub struct EventReportGroups {
+    pub by_sender: BTreeMap<OwnedUserId, Vec<EventReport>>,
+    pub by_room: BTreeMap<OwnedRoomId, Vec<EventReport>>,
+}
+
+impl EventReportGroups {
+    pub fn new(reports: impl IntoIterator<Item = EventReport>) -> Self {
+        let mut groups = Self::default();
+        for report in reports {
+            groups
+                .by_sender
+                .entry(report.sender.clone())
+                .or_default()
+                .push(report.clone());
+            groups
+                .by_room
+                .entry(report.room_id.clone())
+                .or_default()
+                .push(report);
+        }
+        groups
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EventReportDetails {
+    pub report: SingleEventReport,
+    /// The events surrounding the reported event.
+    pub context: EventContext,
+}
+
+#[derive(Debug, Clone)]
+pub enum ReportAction {
+    /// Delete the report. Use this once the report has been dealt with or found to be spurious.
+    Dismiss,
+    /// Redact the reported event.
+    Redact {
+        reason: Option<String>,
+    },
+    ShadowBanSender,
+    DeactivateSender {
+        erase: bool,
+    },
+    /// Quarantine any media referenced by the reported event.
+    QuarantineMedia,
+}
+
+impl SynapseClient {
+    /// Collect every event report matching `query` and group them by sender and room.
+    pub async fn group_event_reports(
+        &self,
+        query: GetEventReportsQuery<'_>,
+    ) -> Result<EventReportGroups> {
+        let reports: Vec<EventReport> = self.stream_event_reports(query).try_collect().await?;
+        Ok(EventReportGroups::new(reports))
+    }
+
+    /// Get a report along with the reported event and the events surrounding it.
+    pub async fn get_event_report_details(&self, report_id: i32) -> Result<EventReportDetails> {
+        let report = self.get_event_report(report_id).await?;
+        let context = self
+            .get_room_event_context(&report.room_id, &report.event_id)
+            .await?;
+        Ok(EventReportDetails { report, context })
+    }
+
+    /// Act on an event report.
+    ///
+    /// Redacting the event requires the admin user to be able to redact events in the room,
+    /// see `redact_event`.
+    ///
+    /// ```rs
+    /// let details = client.get_event_report_details(report_id).await?;
+    /// for action in [
+    ///     ReportAction::Redact { reason: Some("spam".to_owned()) },
+    ///     ReportAction::QuarantineMedia,
+    ///     ReportAction::ShadowBanSender,
+    ///     ReportAction::Dismiss,
+    /// ] {
+    ///     client.apply_report_action(&details.report, &action).await?;
+    /// }
+    /// ```
+    pub async fn apply_report_action(
+        &self,
+        report: &SingleEventReport,
+        action: &ReportAction,
+    ) -> Result<()> {
+        match action {
+            ReportAction::Dismiss => self.delete_event_report(report.id).await,
+            ReportAction::Redact { reason } => self
+                .redact_event(&report.room_id, &report.event_id, reason.as_deref())
+                .await
+                .map(|_| ()),
+            ReportAction::ShadowBanSender => self.shadow_ban_user(&report.sender).await,
+            ReportAction::DeactivateSender { erase } => {
+                self.deactivate_user(&report.sender, *erase).await
+            }
+            ReportAction::QuarantineMedia => {
+                for mxc in report.media_urls() {
+                    self.quarantine_media(&mxc).await?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use std::{fmt::Display, time::SystemTime};
 
 use reqwest::{header::HeaderMap, Client};
 use serde::Deserialize;
@@ -37,15 +37,21 @@ pub mod statistics;
 /// https://matrix-org.github.io/synapse/latest/admin_api/user_admin_api.html
 pub mod users;
 
+mod dates;
+mod pagination;
+mod polling;
+
 mod prelude {
     pub use std::time::SystemTime;
 
-    pub use matrix_sdk::ruma::{EventId, OwnedEventId, OwnedRoomId, OwnedUserId, RoomId, UserId};
+    pub use matrix_sdk::ruma::{
+        EventId, MxcUri, OwnedEventId, OwnedMxcUri, OwnedRoomAliasId, OwnedRoomId, OwnedUserId,
+        RoomAliasId, RoomId, UserId,
+    };
     pub use serde_with::TimestampMilliSeconds;
-    pub use thiserror::Error;
     pub use typed_builder::TypedBuilder;
 
-    pub use crate::{endpoint, execute, MatrixResult, Result, SynapseClient};
+    pub use crate::{endpoint, execute, MatrixResult, Result, SynapseClient, TimeWindow};
 
     #[derive(Deserialize)]
     /// Quite a few endpoints return `{}` as a response, use this in those cases.
@@ -79,6 +85,13 @@ pub struct SynapseClient {
     api_port: u16,
 }
 
+/// A range of time, such as the period to collect statistics or event reports over.
+#[derive(Debug, Clone, Copy)]
+pub struct TimeWindow {
+    pub from: SystemTime,
+    pub until: SystemTime,
+}
+
 /// An error encountered during a Synapse API request.
 #[derive(Debug, Error)]
 pub enum SynapseError {
@@ -92,6 +105,12 @@ pub enum SynapseError {
     Matrix(#[from] MatrixError),
     #[error("request succeeded but response was not recognized: {0}")]
     UnknownResponse(serde_json::Value),
+    #[error("invalid MXC URI: {0}")]
+    InvalidMxcUri(#[from] matrix_sdk::ruma::MxcUriError),
+    #[error("template references unknown variable `{0}`")]
+    UnknownTemplateVariable(String),
+    #[error("timed out waiting for `{0}` to finish")]
+    Timeout(String),
 }
 
 pub type Result<T> = ::std::result::Result<T, SynapseError>;
@@ -113,8 +132,10 @@ impl SynapseClient {
 #[derive(Debug, Deserialize)]
 #[serde(untagged)]
 pub enum MatrixResult<T> {
-    Ok(T),
+    // Errors are tried first, as lenient response types such as `EmptyObject` would otherwise
+    // also match an error.
     Err(MatrixError),
+    Ok(T),
     _Unknown(serde_json::Value),
 }
 
@@ -125,6 +146,8 @@ pub struct MatrixError {
     pub code: String,
     #[serde(rename = "error")]
     pub message: String,
+    /// How long to wait before retrying, if the request was rate limited.
+    pub retry_after_ms: Option<u64>,
 }
 
 impl Display for MatrixError {
@@ -166,4 +189,13 @@ macro_rules! endpoint {
     (v2 $client:ident $path:expr) => {
         endpoint!(_inner $client "v2" $path)
     };
+    // Some admin operations are only exposed through the client-server API, which Synapse
+    // permits server admins to use on rooms and aliases they don't otherwise have access to.
+    (client $client:ident $path:expr) => {
+        format!(
+            "{}:{}{}{}",
+            $client.api_url, $client.api_port, "/_matrix/client/v3", $path,
+        )
+        .parse::<::reqwest::Url>()?
+    };
 }
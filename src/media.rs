@@ -1,11 +1,19 @@
+mod info;
+pub use info::*;
+
 mod query;
 pub use query::*;
 
 mod quarantine;
-pub use quarantine::*;
 
 mod delete;
 pub use delete::*;
 
 mod purge;
 pub use purge::*;
+
+mod retention;
+pub use retention::*;
+
+mod room_audit;
+pub use room_audit::*;
@@ -10,23 +10,21 @@ pub struct MediaDeletionResponse {
 #[skip_serializing_none]
 #[derive(Debug, Clone, Serialize, TypedBuilder)]
 pub struct DeleteMediaQuery {
+    /// Delete media which was last accessed before this time.
     #[serde_as(as = "TimestampMilliSeconds<i64>")]
-    before_ts: SystemTime,
+    pub before_ts: SystemTime,
     #[builder(default, setter(strip_option))]
-    size_gt: Option<usize>,
+    pub size_gt: Option<usize>,
     #[builder(default, setter(strip_option))]
-    keep_profiles: Option<bool>,
+    pub keep_profiles: Option<bool>,
 }
 
 impl SynapseClient {
-    pub async fn delete_media(
-        &self,
-        homeserver: &str,
-        media_id: &str,
-    ) -> Result<MediaDeletionResponse> {
+    pub async fn delete_media(&self, mxc: &MxcUri) -> Result<MediaDeletionResponse> {
+        let (server_name, media_id) = mxc.parts()?;
         execute!(
             self.inner
-                .delete(endpoint!(self format!("/media/{homeserver}/{media_id}")))
+                .delete(endpoint!(self format!("/media/{server_name}/{media_id}")))
                 .send()
                 .await?
                 .json::<MatrixResult<MediaDeletionResponse>>()
@@ -0,0 +1,94 @@
+use matrix_sdk::ruma::OwnedServerName;
+use serde_with::DefaultOnNull;
+
+use crate::prelude::*;
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediaInfo {
+    /// The server the media was uploaded to. Only returned by `get_media_info`.
+    pub media_origin: Option<OwnedServerName>,
+    /// The local user who uploaded the media, if any. Only returned by `get_media_info`.
+    pub user_id: Option<OwnedUserId>,
+    /// The ID of the media, without the server name.
+    pub media_id: String,
+    /// The MIME type of the media.
+    pub media_type: String,
+    /// The size of the media in bytes, or `None` if it is still being uploaded.
+    pub media_length: Option<usize>,
+    /// The file name the media was uploaded with.
+    pub upload_name: Option<String>,
+    /// The time at which the media was uploaded.
+    #[serde_as(as = "TimestampMilliSeconds<i64>")]
+    pub created_ts: SystemTime,
+    /// The time at which the media was last downloaded.
+    #[serde_as(as = "Option<TimestampMilliSeconds<i64>>")]
+    pub last_access_ts: Option<SystemTime>,
+    /// The user who quarantined the media, if it is quarantined.
+    pub quarantined_by: Option<OwnedUserId>,
+    /// Whether the media is protected from being quarantined.
+    /// Synapse leaves this out for remote media, which can't be protected.
+    #[serde(default)]
+    #[serde_as(deserialize_as = "DefaultOnNull")]
+    pub safe_from_quarantine: bool,
+}
+
+impl SynapseClient {
+    /// Get the metadata of a single piece of local or cached remote media.
+    pub async fn get_media_info(&self, mxc: &MxcUri) -> Result<MediaInfo> {
+        #[derive(Deserialize)]
+        struct Response {
+            media_info: MediaInfo,
+        }
+        let (server_name, media_id) = mxc.parts()?;
+        execute!(
+            self.inner
+                .get(endpoint!(self format!("/media/{server_name}/{media_id}")))
+                .send()
+                .await?
+                .json::<MatrixResult<Response>>()
+                .await?;
+            res => res.media_info
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_remote_media() {
+        // synapse leaves out null fields, and remote media has no uploader or protection
+        let info: MediaInfo = serde_json::from_value(serde_json::json!({
+            "media_origin": "remote.example",
+            "media_id": "abcdefg12345",
+            "media_type": "image/png",
+            "media_length": 67,
+            "upload_name": "test.png",
+            "created_ts": 1700000000000u64,
+            "last_access_ts": 1700000100000u64
+        }))
+        .unwrap();
+        assert_eq!(info.media_length, Some(67));
+        assert_eq!(info.user_id, None);
+        assert!(!info.safe_from_quarantine);
+        assert_eq!(info.quarantined_by, None);
+    }
+
+    #[test]
+    fn reads_media_still_being_uploaded() {
+        let info: MediaInfo = serde_json::from_value(serde_json::json!({
+            "media_origin": "local.example",
+            "user_id": "@alice:local.example",
+            "media_id": "abcdefg12345",
+            "media_type": "image/png",
+            "created_ts": 1700000000000u64,
+            "safe_from_quarantine": null
+        }))
+        .unwrap();
+        assert_eq!(info.media_length, None);
+        assert_eq!(info.last_access_ts, None);
+        assert!(!info.safe_from_quarantine);
+    }
+}
@@ -1,8 +1,31 @@
-use std::time::UNIX_EPOCH;
+use std::{
+    collections::{BTreeMap, HashSet},
+    time::UNIX_EPOCH,
+};
 
-use crate::prelude::*;
+use futures_util::TryStreamExt;
+use matrix_sdk::ruma::{OwnedServerName, ServerName};
+
+use crate::{prelude::*, rooms::GetRoomsQuery, statistics::MediaUsage, SynapseError};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediaCachePurgePlan {
+    /// The remote media the purge would remove from each origin server.
+    pub origins: BTreeMap<OwnedServerName, MediaUsage>,
+}
+
+impl MediaCachePurgePlan {
+    pub fn total(&self) -> MediaUsage {
+        self.origins.values().copied().sum()
+    }
+}
 
 impl SynapseClient {
+    /// Purge cached copies of remote media which were last accessed before `before_ts`.
+    /// Returns the number of media items purged.
+    ///
+    /// Synapse only supports filtering the purge by last access time. Use
+    /// `plan_media_cache_purge` to see which servers the purged media comes from.
     pub async fn purge_media_cache(&self, before_ts: SystemTime) -> Result<usize> {
         // SAFETY: it is impossible for UNIX_EPOCH to be in the future
         let before_ts = before_ts.duration_since(UNIX_EPOCH).unwrap().as_millis();
@@ -23,4 +46,53 @@ impl SynapseClient {
             res => res.deleted
         )
     }
+
+    /// Estimate how much remote media `purge_media_cache` would purge from each origin server,
+    /// optionally only counting media from `origin`.
+    ///
+    /// Synapse has no endpoint listing cached remote media, so this looks up the remote media
+    /// referenced in every room, which can take a long time on large servers. Media which isn't
+    /// referenced in any room is not counted.
+    pub async fn plan_media_cache_purge(
+        &self,
+        before_ts: SystemTime,
+        origin: Option<&ServerName>,
+    ) -> Result<MediaCachePurgePlan> {
+        let room_ids: Vec<OwnedRoomId> = self
+            .stream_rooms(GetRoomsQuery::builder().build())
+            .map_ok(|room| room.room_id)
+            .try_collect()
+            .await?;
+
+        let mut seen = HashSet::new();
+        let mut origins = BTreeMap::<OwnedServerName, MediaUsage>::new();
+        for room_id in room_ids {
+            for mxc in self.get_media_for_room(&room_id).await?.remote {
+                let Ok(server_name) = mxc.server_name() else {
+                    continue;
+                };
+                if origin.is_some_and(|origin| origin != server_name) {
+                    continue;
+                }
+                let server_name = server_name.to_owned();
+                if !seen.insert(mxc.clone()) {
+                    continue;
+                }
+                let info = match self.get_media_info(&mxc).await {
+                    Ok(info) => info,
+                    // media which was never fetched by this server isn't cached
+                    Err(SynapseError::Matrix(err)) if err.code == "M_NOT_FOUND" => continue,
+                    Err(err) => return Err(err),
+                };
+                if info.last_access_ts.is_some_and(|ts| ts < before_ts) {
+                    origins
+                        .entry(server_name)
+                        .or_default()
+                        .record(1, info.media_length.unwrap_or_default());
+                }
+            }
+        }
+
+        Ok(MediaCachePurgePlan { origins })
+    }
 }
@@ -6,10 +6,11 @@ struct MassQuarantineResponse {
 }
 
 impl SynapseClient {
-    pub async fn quarantine_media(&self, homeserver: &str, media_id: &str) -> Result<()> {
+    pub async fn quarantine_media(&self, mxc: &MxcUri) -> Result<()> {
+        let (server_name, media_id) = mxc.parts()?;
         execute!(
             self.inner
-                .post(endpoint!(self format!("/media/quarantine/{homeserver}/{media_id}")))
+                .post(endpoint!(self format!("/media/quarantine/{server_name}/{media_id}")))
                 .send()
                 .await?
                 .json::<MatrixResult<EmptyObject>>()
@@ -18,10 +19,11 @@ impl SynapseClient {
         )
     }
 
-    pub async fn unquarantine_media(&self, homeserver: &str, media_id: &str) -> Result<()> {
+    pub async fn unquarantine_media(&self, mxc: &MxcUri) -> Result<()> {
+        let (server_name, media_id) = mxc.parts()?;
         execute!(
             self.inner
-                .post(endpoint!(self format!("/media/unquarantine/{homeserver}/{media_id}")))
+                .post(endpoint!(self format!("/media/unquarantine/{server_name}/{media_id}")))
                 .send()
                 .await?
                 .json::<MatrixResult<EmptyObject>>()
@@ -56,6 +58,8 @@ impl SynapseClient {
         )
     }
 
+    /// Protect a piece of local media from being quarantined. Only local media can be
+    /// protected, so this takes the ID of the media without a server name.
     pub async fn protect_media_from_quarantine(&self, media_id: &str) -> Result<()> {
         execute!(
             self.inner
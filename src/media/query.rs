@@ -2,8 +2,8 @@ use crate::prelude::*;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MediaMXCs {
-    pub local: Vec<String>,
-    pub remote: Vec<String>,
+    pub local: Vec<OwnedMxcUri>,
+    pub remote: Vec<OwnedMxcUri>,
 }
 
 impl SynapseClient {
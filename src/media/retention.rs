@@ -0,0 +1,198 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, UNIX_EPOCH},
+};
+
+use futures_util::TryStreamExt;
+
+use crate::{prelude::*, statistics::MediaStatisticsQuery, users::UserMediaQuery};
+
+use super::DeleteMediaQuery;
+
+#[derive(Debug, Clone)]
+pub enum MediaRetentionRule {
+    /// Purge cached copies of remote media which haven't been accessed within `unused_for`.
+    Remote { unused_for: Duration },
+    /// Delete local media which hasn't been accessed within `unused_for`. Synapse decides by
+    /// when the media was last downloaded, not when it was uploaded, so old media which is
+    /// still in use is kept.
+    Local {
+        unused_for: Duration,
+        /// Only delete media larger than this many bytes.
+        min_size: Option<usize>,
+        /// Keep media which is used as a user's avatar or as a room's avatar.
+        keep_profiles: bool,
+    },
+}
+
+impl MediaRetentionRule {
+    fn cutoff(&self, now: SystemTime) -> SystemTime {
+        let unused_for = match self {
+            MediaRetentionRule::Remote { unused_for }
+            | MediaRetentionRule::Local { unused_for, .. } => *unused_for,
+        };
+        now.checked_sub(unused_for).unwrap_or(UNIX_EPOCH)
+    }
+}
+
+/// A set of rules deciding which media is deleted.
+///
+/// ```rs
+/// const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+/// let policy = MediaRetentionPolicy::new()
+///     .rule(MediaRetentionRule::Remote { unused_for: 30 * DAY })
+///     .rule(MediaRetentionRule::Local {
+///         unused_for: 365 * DAY,
+///         min_size: Some(10 * 1024 * 1024),
+///         keep_profiles: true,
+///     });
+/// for estimate in client.estimate_media_retention(&policy).await? {
+///     println!("{:?}: {:?} bytes", estimate.rule, estimate.media_length);
+/// }
+/// let outcome = client.apply_media_retention(&policy).await?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MediaRetentionPolicy {
+    pub rules: Vec<MediaRetentionRule>,
+}
+
+impl MediaRetentionPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn rule(mut self, rule: MediaRetentionRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MediaRetentionEstimate {
+    pub rule: MediaRetentionRule,
+    /// The number of local media items uploaded before the rule's cutoff, or `None` if it can't
+    /// be estimated. Media statistics are only kept for local media and can only be filtered
+    /// by upload time, while the rule deletes by last access time and also applies `min_size`
+    /// and `keep_profiles`. This is an upper bound on what the rule deletes, not a prediction.
+    pub media_count: Option<usize>,
+    /// The number of bytes the rule would delete at most. See `media_count`.
+    pub media_length: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MediaRetentionOutcome {
+    /// The IDs of the local media which were deleted.
+    pub deleted_media: Vec<String>,
+    /// The number of cached remote media items which were purged.
+    pub purged_remote_media: usize,
+    /// The total size in bytes of the deleted local media. Media which wasn't uploaded by a
+    /// local user isn't counted.
+    pub deleted_bytes: usize,
+}
+
+impl SynapseClient {
+    /// Estimate how much media each rule of `policy` would delete.
+    pub async fn estimate_media_retention(
+        &self,
+        policy: &MediaRetentionPolicy,
+    ) -> Result<Vec<MediaRetentionEstimate>> {
+        let now = SystemTime::now();
+        let mut estimates = Vec::with_capacity(policy.rules.len());
+        for rule in &policy.rules {
+            let (media_count, media_length) = match rule {
+                MediaRetentionRule::Remote { .. } => (None, None),
+                MediaRetentionRule::Local { .. } => {
+                    let (count, length) = self.local_media_totals(Some(rule.cutoff(now))).await?;
+                    (Some(count), Some(length))
+                }
+            };
+            estimates.push(MediaRetentionEstimate {
+                rule: rule.clone(),
+                media_count,
+                media_length,
+            });
+        }
+        Ok(estimates)
+    }
+
+    /// Apply every rule of `policy` in order.
+    pub async fn apply_media_retention(
+        &self,
+        policy: &MediaRetentionPolicy,
+    ) -> Result<MediaRetentionOutcome> {
+        let now = SystemTime::now();
+        let mut outcome = MediaRetentionOutcome::default();
+        for rule in &policy.rules {
+            let cutoff = rule.cutoff(now);
+            match rule {
+                MediaRetentionRule::Remote { .. } => {
+                    outcome.purged_remote_media += self.purge_media_cache(cutoff).await?;
+                }
+                MediaRetentionRule::Local {
+                    min_size,
+                    keep_profiles,
+                    ..
+                } => {
+                    // media last accessed before the cutoff was also uploaded before it
+                    let lengths = self.local_media_lengths_before(cutoff).await?;
+                    let query = DeleteMediaQuery {
+                        before_ts: cutoff,
+                        size_gt: *min_size,
+                        keep_profiles: Some(*keep_profiles),
+                    };
+                    let deleted = self.delete_media_where(query).await?;
+
+                    outcome.deleted_bytes += deleted
+                        .deleted_media
+                        .iter()
+                        .filter_map(|media_id| lengths.get(media_id))
+                        .sum::<usize>();
+                    outcome.deleted_media.extend(deleted.deleted_media);
+                }
+            }
+        }
+        Ok(outcome)
+    }
+
+    /// The size of each piece of local media uploaded before `until`, keyed by media ID.
+    async fn local_media_lengths_before(
+        &self,
+        until: SystemTime,
+    ) -> Result<HashMap<String, usize>> {
+        let query = MediaStatisticsQuery {
+            until_ts: Some(until),
+            ..MediaStatisticsQuery::builder().build()
+        };
+        let uploaders: Vec<OwnedUserId> = self
+            .stream_media_statistics(query)
+            .map_ok(|user| user.user_id)
+            .try_collect()
+            .await?;
+
+        let mut lengths = HashMap::new();
+        for user_id in &uploaders {
+            self.stream_user_media(user_id, UserMediaQuery::default())
+                .try_for_each(|media| {
+                    if media.created_ts < until {
+                        lengths.insert(media.media_id, media.media_length.unwrap_or_default());
+                    }
+                    async { Ok(()) }
+                })
+                .await?;
+        }
+        Ok(lengths)
+    }
+
+    /// Sum the count and size of all local media uploaded before `until`.
+    async fn local_media_totals(&self, until: Option<SystemTime>) -> Result<(usize, usize)> {
+        let query = MediaStatisticsQuery {
+            until_ts: until,
+            ..MediaStatisticsQuery::builder().build()
+        };
+        self.stream_media_statistics(query)
+            .try_fold((0, 0), |(count, length), user| async move {
+                Ok((count + user.media_count, length + user.media_length))
+            })
+            .await
+    }
+}
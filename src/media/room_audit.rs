@@ -0,0 +1,97 @@
+use crate::{prelude::*, SynapseError};
+
+use super::MediaInfo;
+
+#[derive(Debug)]
+pub struct RoomMediaItem {
+    pub mxc: OwnedMxcUri,
+    /// Whether the media was uploaded to this server.
+    pub local: bool,
+    /// The media's metadata, or the error encountered while fetching it. Remote media which
+    /// was never cached by this server has no metadata.
+    pub info: Result<MediaInfo>,
+}
+
+impl RoomMediaItem {
+    /// Whether the media is known to have an `image/*` content type.
+    pub fn is_image(&self) -> bool {
+        self.info
+            .as_ref()
+            .is_ok_and(|info| info.media_type.starts_with("image/"))
+    }
+
+    /// Whether the media is known to have been uploaded by `user_id`.
+    pub fn is_uploaded_by(&self, user_id: &UserId) -> bool {
+        self.info
+            .as_ref()
+            .is_ok_and(|info| info.user_id.as_deref() == Some(user_id))
+    }
+}
+
+#[derive(Debug)]
+pub enum QuarantineOutcome {
+    /// The media didn't match the predicate and was left alone.
+    Skipped,
+    Quarantined,
+    Failed(SynapseError),
+}
+
+#[derive(Debug)]
+pub struct RoomMediaQuarantineResult {
+    pub item: RoomMediaItem,
+    pub outcome: QuarantineOutcome,
+}
+
+impl SynapseClient {
+    /// Get every piece of media referenced in a room along with its metadata.
+    pub async fn audit_room_media(&self, room_id: &RoomId) -> Result<Vec<RoomMediaItem>> {
+        let mxcs = self.get_media_for_room(room_id).await?;
+        let media = mxcs
+            .local
+            .into_iter()
+            .map(|mxc| (mxc, true))
+            .chain(mxcs.remote.into_iter().map(|mxc| (mxc, false)));
+
+        let mut items = Vec::new();
+        for (mxc, local) in media {
+            let info = self.get_media_info(&mxc).await;
+            items.push(RoomMediaItem { mxc, local, info });
+        }
+        Ok(items)
+    }
+
+    /// Quarantine the media in a room for which `predicate` returns `true`, returning what
+    /// happened to each piece of media.
+    ///
+    /// Unlike `quarantine_all_media_in_room`, this quarantines each piece of media separately.
+    ///
+    /// ```rs
+    /// let results = client
+    ///     .quarantine_room_media_where(&room_id, |item| item.is_uploaded_by(&spammer))
+    ///     .await?;
+    /// for result in results {
+    ///     println!("{}: {:?}", result.item.mxc, result.outcome);
+    /// }
+    /// ```
+    pub async fn quarantine_room_media_where(
+        &self,
+        room_id: &RoomId,
+        predicate: impl Fn(&RoomMediaItem) -> bool,
+    ) -> Result<Vec<RoomMediaQuarantineResult>> {
+        let items = self.audit_room_media(room_id).await?;
+
+        let mut results = Vec::with_capacity(items.len());
+        for item in items {
+            let outcome = if !predicate(&item) {
+                QuarantineOutcome::Skipped
+            } else {
+                match self.quarantine_media(&item.mxc).await {
+                    Ok(()) => QuarantineOutcome::Quarantined,
+                    Err(err) => QuarantineOutcome::Failed(err),
+                }
+            };
+            results.push(RoomMediaQuarantineResult { item, outcome });
+        }
+        Ok(results)
+    }
+}
@@ -0,0 +1,30 @@
+use std::future::Future;
+
+use futures_util::{stream, Stream, TryStreamExt};
+
+use crate::Result;
+
+/// Stream every item of a paginated endpoint. `fetch_page` gets the page for a query, and returns
+/// its items along with the query for the next page, or `None` if it was the last page.
+pub(crate) fn paginate<'a, Q, T, F, Fut>(
+    query: Q,
+    mut fetch_page: F,
+) -> impl Stream<Item = Result<T>> + 'a
+where
+    Q: 'a,
+    T: 'a,
+    F: FnMut(Q) -> Fut + 'a,
+    Fut: Future<Output = Result<(Vec<T>, Option<Q>)>> + 'a,
+{
+    stream::try_unfold(Some(query), move |query| {
+        let page = query.map(&mut fetch_page);
+        async move {
+            let Some(page) = page else {
+                return Result::Ok(None);
+            };
+            let (items, next) = page.await?;
+            Ok(Some((stream::iter(items.into_iter().map(Ok)), next)))
+        }
+    })
+    .try_flatten()
+}
@@ -0,0 +1,33 @@
+use std::{future::Future, time::Duration};
+
+use crate::{prelude::PurgeStatus, Result, SynapseError};
+
+/// Poll the status of a purge or deletion every `poll_interval` until it has either completed
+/// or failed, giving up after `timeout`.
+pub(crate) async fn wait_for_purge<T, F, Fut>(
+    id: &str,
+    poll_interval: Duration,
+    timeout: Duration,
+    mut get_status: F,
+    status: impl Fn(&T) -> PurgeStatus,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let wait = async {
+        loop {
+            let current = get_status().await?;
+            if matches!(
+                status(&current),
+                PurgeStatus::Complete | PurgeStatus::Failed
+            ) {
+                return Ok(current);
+            }
+            tokio::time::sleep(poll_interval).await;
+        }
+    };
+    tokio::time::timeout(timeout, wait)
+        .await
+        .map_err(|_| SynapseError::Timeout(id.to_owned()))?
+}
@@ -1,10 +1,27 @@
-use crate::prelude::*;
+use std::time::Duration;
+
+use crate::{polling::wait_for_purge, prelude::*};
+
+mod retention;
+pub use retention::*;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PurgeHistoryResponse {
     pub purge_id: String,
 }
 
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, TypedBuilder)]
+pub struct PurgeHistoryBody {
+    /// Purge events received before this time.
+    #[serde_as(as = "TimestampMilliSeconds<i64>")]
+    pub purge_up_to_ts: SystemTime,
+    /// Also purge events sent by local users, not just remote ones.
+    #[builder(default, setter(strip_option))]
+    pub delete_local_events: Option<bool>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PurgeHistoryStatus {
     pub status: PurgeStatus,
@@ -18,24 +35,47 @@ impl SynapseClient {
         event_id: Option<&EventId>,
         delete_local_events: Option<bool>,
     ) -> Result<PurgeHistoryResponse> {
+        #[skip_serializing_none]
         #[derive(Serialize)]
         struct RequestBody {
-            pub delete_local_events: bool,
+            pub delete_local_events: Option<bool>,
         }
 
-        let mut req = self.inner.post(endpoint!(self format!(
-            "/purge_history/{room_id}{}",
-            event_id.map(|e| format!("/{}", e)).unwrap_or_default()
-        )));
-
-        if let Some(delete_local_events) = delete_local_events {
-            req = req.json(&RequestBody {
-                delete_local_events,
-            });
-        }
+        execute!(
+            self.inner
+                .post(endpoint!(self format!(
+                    "/purge_history/{room_id}{}",
+                    event_id.map(|e| format!("/{}", e)).unwrap_or_default()
+                )))
+                .json(&RequestBody {
+                    delete_local_events,
+                })
+                .send()
+                .await?
+                .json::<MatrixResult<PurgeHistoryResponse>>()
+                .await?
+        )
+    }
 
+    /// Purge the history of a room up to a point in time, rather than up to an event.
+    ///
+    /// ```rs
+    /// let body = PurgeHistoryBody::builder()
+    ///     .purge_up_to_ts(SystemTime::now() - Duration::from_secs(30 * 24 * 60 * 60))
+    ///     .delete_local_events(true)
+    ///     .build();
+    /// let purge = client.purge_room_history_before(&room_id, &body).await?;
+    /// ```
+    pub async fn purge_room_history_before(
+        &self,
+        room_id: &RoomId,
+        body: &PurgeHistoryBody,
+    ) -> Result<PurgeHistoryResponse> {
         execute!(
-            req.send()
+            self.inner
+                .post(endpoint!(self format!("/purge_history/{room_id}")))
+                .json(body)
+                .send()
                 .await?
                 .json::<MatrixResult<PurgeHistoryResponse>>()
                 .await?
@@ -52,4 +92,22 @@ impl SynapseClient {
                 .await?
         )
     }
+
+    /// Poll `get_purge_status` until the purge has either completed or failed, or until
+    /// `timeout` has passed.
+    pub async fn wait_for_history_purge(
+        &self,
+        purge_id: &str,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<PurgeHistoryStatus> {
+        wait_for_purge(
+            purge_id,
+            poll_interval,
+            timeout,
+            || self.get_purge_status(purge_id),
+            |status: &PurgeHistoryStatus| status.status,
+        )
+        .await
+    }
 }
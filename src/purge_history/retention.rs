@@ -0,0 +1,198 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::Path,
+    time::{Duration, UNIX_EPOCH},
+};
+
+use futures_util::TryStreamExt;
+
+use crate::{
+    prelude::*,
+    rooms::{GetRoomsQuery, TimestampToEventQuery},
+    SynapseError,
+};
+
+#[derive(Debug, Clone)]
+pub enum RoomSelector {
+    Room(OwnedRoomId),
+    /// Every room whose name, canonical alias or ID contains this string.
+    Search(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct HistoryRetentionRule {
+    pub rooms: RoomSelector,
+    /// Purge events older than this.
+    pub max_age: Duration,
+    /// Also purge events sent by local users, not just remote ones.
+    pub delete_local_events: bool,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoomRetentionRun {
+    #[serde_as(as = "TimestampMilliSeconds<i64>")]
+    pub ran_at: SystemTime,
+    /// The newest event which was purged, or `None` if there were no events old enough.
+    pub purged_up_to: Option<OwnedEventId>,
+    pub status: Option<PurgeStatus>,
+    pub error: Option<String>,
+}
+
+/// The last retention run of each room, saved between runs so rooms aren't purged more often
+/// than necessary.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HistoryRetentionState {
+    pub last_runs: BTreeMap<OwnedRoomId, RoomRetentionRun>,
+}
+
+impl HistoryRetentionState {
+    /// Load the state from a JSON file, or start with an empty state if it doesn't exist yet.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        match fs::read(path) {
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, serde_json::to_vec_pretty(self)?)
+    }
+}
+
+#[derive(Debug, Clone, TypedBuilder)]
+pub struct HistoryRetentionScheduler {
+    pub rules: Vec<HistoryRetentionRule>,
+    /// Rooms which were purged more recently than this are skipped.
+    #[builder(default = Duration::from_secs(23 * 60 * 60))]
+    pub min_interval: Duration,
+    /// How long to wait between checks of a purge's status.
+    #[builder(default = Duration::from_secs(1))]
+    pub poll_interval: Duration,
+    /// How long to wait for each purge to finish before giving up on tracking it.
+    #[builder(default = Duration::from_secs(60 * 60))]
+    pub purge_timeout: Duration,
+}
+
+#[derive(Debug)]
+pub struct RoomRetentionOutcome {
+    pub room_id: OwnedRoomId,
+    /// `Ok(None)` if the room was skipped because it was purged recently.
+    pub result: Result<Option<RoomRetentionRun>>,
+}
+
+impl SynapseClient {
+    /// Purge old history from every room matched by `scheduler`'s rules, recording each run in
+    /// `state`. Meant to be run regularly, e.g. nightly from cron:
+    ///
+    /// ```rs
+    /// const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+    /// let scheduler = HistoryRetentionScheduler::builder()
+    ///     .rules(vec![HistoryRetentionRule {
+    ///         rooms: RoomSelector::Search("support".to_owned()),
+    ///         max_age: 90 * DAY,
+    ///         delete_local_events: true,
+    ///     }])
+    ///     .build();
+    /// let mut state = HistoryRetentionState::load("retention.json")?;
+    /// let outcomes = client.run_history_retention(&scheduler, &mut state).await?;
+    /// state.save("retention.json")?;
+    /// ```
+    pub async fn run_history_retention(
+        &self,
+        scheduler: &HistoryRetentionScheduler,
+        state: &mut HistoryRetentionState,
+    ) -> Result<Vec<RoomRetentionOutcome>> {
+        let mut outcomes = Vec::new();
+        for rule in &scheduler.rules {
+            let room_ids = match &rule.rooms {
+                RoomSelector::Room(room_id) => vec![room_id.clone()],
+                RoomSelector::Search(term) => {
+                    let query = GetRoomsQuery::builder().search_term(term.clone()).build();
+                    self.stream_rooms(query)
+                        .map_ok(|room| room.room_id)
+                        .try_collect()
+                        .await?
+                }
+            };
+
+            for room_id in room_ids {
+                // failed purges are retried on the next run
+                let ran_recently = state.last_runs.get(&room_id).is_some_and(|run| {
+                    !matches!(run.status, Some(PurgeStatus::Failed))
+                        && run
+                            .ran_at
+                            .elapsed()
+                            .is_ok_and(|elapsed| elapsed < scheduler.min_interval)
+                });
+                if ran_recently {
+                    outcomes.push(RoomRetentionOutcome {
+                        room_id,
+                        result: Ok(None),
+                    });
+                    continue;
+                }
+
+                let result = self
+                    .purge_room_history_older_than(&room_id, rule, scheduler)
+                    .await;
+                if let Ok(run) = &result {
+                    state.last_runs.insert(room_id.clone(), run.clone());
+                }
+                outcomes.push(RoomRetentionOutcome {
+                    room_id,
+                    result: result.map(Some),
+                });
+            }
+        }
+        Ok(outcomes)
+    }
+
+    async fn purge_room_history_older_than(
+        &self,
+        room_id: &RoomId,
+        rule: &HistoryRetentionRule,
+        scheduler: &HistoryRetentionScheduler,
+    ) -> Result<RoomRetentionRun> {
+        let ran_at = SystemTime::now();
+        let query = TimestampToEventQuery::builder()
+            .timestamp(ran_at.checked_sub(rule.max_age).unwrap_or(UNIX_EPOCH))
+            .direction(SortDirection::Descending)
+            .build();
+        let cutoff = match self.timestamp_to_event(room_id, &query).await {
+            Ok(event_id) => event_id,
+            // synapse responds with a 404 when there are no events before the timestamp
+            Err(SynapseError::Matrix(err)) if err.code == "M_NOT_FOUND" => None,
+            Err(err) => return Err(err),
+        };
+
+        let Some(event_id) = cutoff else {
+            return Ok(RoomRetentionRun {
+                ran_at,
+                purged_up_to: None,
+                status: None,
+                error: None,
+            });
+        };
+
+        let purge = self
+            .purge_room_history(room_id, Some(&event_id), Some(rule.delete_local_events))
+            .await?;
+        let status = self
+            .wait_for_history_purge(
+                &purge.purge_id,
+                scheduler.poll_interval,
+                scheduler.purge_timeout,
+            )
+            .await?;
+
+        Ok(RoomRetentionRun {
+            ran_at,
+            purged_up_to: Some(event_id),
+            status: Some(status.status),
+            error: status.error,
+        })
+    }
+}
@@ -18,13 +18,11 @@ pub struct RegistrationToken {
 
 impl RegistrationToken {
     pub fn is_valid(&self) -> bool {
-        let expired = self
-            .expiry_time
-            .map_or(false, |exp| SystemTime::now() > exp);
+        let expired = self.expiry_time.is_some_and(|exp| SystemTime::now() > exp);
 
         let usages_reached = self
             .uses_allowed
-            .map_or(false, |uses| self.completed + self.pending >= uses);
+            .is_some_and(|uses| self.completed + self.pending >= uses);
 
         !expired && !usages_reached
     }
@@ -1,5 +1,8 @@
 use crate::prelude::*;
 
+mod bulk;
+pub use bulk::*;
+
 impl SynapseClient {
     pub async fn add_user_to_room(
         &self,
@@ -0,0 +1,130 @@
+use crate::prelude::*;
+
+#[derive(Debug)]
+pub struct RoomMembershipOutcome {
+    pub room_id: OwnedRoomId,
+    pub result: Result<()>,
+}
+
+#[derive(Debug, Default)]
+pub struct RoomMembershipReport {
+    pub rooms: Vec<RoomMembershipOutcome>,
+}
+
+impl RoomMembershipReport {
+    pub fn failures(&self) -> impl Iterator<Item = &RoomMembershipOutcome> {
+        self.rooms.iter().filter(|room| room.result.is_err())
+    }
+
+    pub fn succeeded(&self) -> impl Iterator<Item = &OwnedRoomId> {
+        self.rooms
+            .iter()
+            .filter(|room| room.result.is_ok())
+            .map(|room| &room.room_id)
+    }
+}
+
+impl SynapseClient {
+    /// Join a local user to every room in `room_ids`, continuing past rooms which fail.
+    ///
+    /// See `add_user_to_room` for the conditions a room must meet.
+    pub async fn add_user_to_rooms(
+        &self,
+        user_id: &UserId,
+        room_ids: impl IntoIterator<Item = OwnedRoomId>,
+    ) -> RoomMembershipReport {
+        let mut report = RoomMembershipReport::default();
+        for room_id in room_ids {
+            let result = self.add_user_to_room(&room_id, user_id).await.map(|_| ());
+            report.rooms.push(RoomMembershipOutcome { room_id, result });
+        }
+        report
+    }
+
+    /// Join a local user to a space and to every room directly in it.
+    ///
+    /// ```rs
+    /// // onboarding
+    /// let report = client.add_user_to_space(&new_hire, &company_space).await?;
+    /// for failure in report.failures() {
+    ///     eprintln!("{}: {:?}", failure.room_id, failure.result);
+    /// }
+    /// ```
+    pub async fn add_user_to_space(
+        &self,
+        user_id: &UserId,
+        space_id: &RoomId,
+    ) -> Result<RoomMembershipReport> {
+        let children = self.get_space_children(space_id).await?;
+        let room_ids = std::iter::once(space_id.to_owned()).chain(children);
+        Ok(self.add_user_to_rooms(user_id, room_ids).await)
+    }
+
+    /// Kick a user from a room as the user who owns this client's access token.
+    ///
+    /// The admin user must be joined to the room with a high enough power level to kick,
+    /// which `join_room_as_admin` can be used to arrange.
+    pub async fn kick_user(
+        &self,
+        room_id: &RoomId,
+        user_id: &UserId,
+        reason: Option<&str>,
+    ) -> Result<()> {
+        #[skip_serializing_none]
+        #[derive(Serialize)]
+        struct RequestBody<'a> {
+            user_id: &'a UserId,
+            reason: Option<&'a str>,
+        }
+        execute!(
+            self.inner
+                .post(endpoint!(client self format!("/rooms/{room_id}/kick")))
+                .json(&RequestBody { user_id, reason })
+                .send()
+                .await?
+                .json::<MatrixResult<EmptyObject>>()
+                .await?;
+            _r => ()
+        )
+    }
+
+    /// Remove a user from every room in `room_ids`, continuing past rooms which fail.
+    ///
+    /// The admin user first joins each room as an admin with `join_room_as_admin`, and stays
+    /// joined to the room afterwards.
+    pub async fn kick_user_from_rooms(
+        &self,
+        user_id: &UserId,
+        room_ids: impl IntoIterator<Item = OwnedRoomId>,
+        reason: Option<&str>,
+    ) -> Result<RoomMembershipReport> {
+        let admin_id = self.whoami().await?;
+        let mut report = RoomMembershipReport::default();
+        for room_id in room_ids {
+            let result = match self.join_room_as(&room_id, &admin_id).await {
+                Ok(()) => self.kick_user(&room_id, user_id, reason).await,
+                Err(err) => Err(err),
+            };
+            report.rooms.push(RoomMembershipOutcome { room_id, result });
+        }
+        Ok(report)
+    }
+
+    /// Remove a user from every room they're joined to. See `kick_user_from_rooms`.
+    ///
+    /// ```rs
+    /// // offboarding
+    /// let report = client
+    ///     .kick_user_from_all_rooms(&user_id, Some("Left the company"))
+    ///     .await?;
+    /// ```
+    pub async fn kick_user_from_all_rooms(
+        &self,
+        user_id: &UserId,
+        reason: Option<&str>,
+    ) -> Result<RoomMembershipReport> {
+        let joined = self.get_user_joined_rooms(user_id).await?;
+        self.kick_user_from_rooms(user_id, joined.joined_rooms, reason)
+            .await
+    }
+}
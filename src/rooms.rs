@@ -8,7 +8,6 @@ mod members;
 pub use members::*;
 
 mod state;
-pub use state::*;
 
 mod messages;
 pub use messages::*;
@@ -23,10 +22,26 @@ mod delete;
 pub use delete::*;
 
 mod admin;
-pub use admin::*;
 
 mod extremities;
 pub use extremities::*;
 
 mod event_context;
 pub use event_context::*;
+
+mod shutdown;
+pub use shutdown::*;
+
+mod directory;
+pub use directory::*;
+
+mod filter;
+pub use filter::*;
+
+mod cleanup;
+pub use cleanup::*;
+
+mod redact;
+
+mod space;
+pub use space::*;
@@ -24,4 +24,37 @@ impl SynapseClient {
             _r => ()
         )
     }
+
+    /// Make the user who owns this client's access token an admin of the room and join them to
+    /// it, so they can act in the room through the client-server API.
+    ///
+    /// `make_room_admin` only raises the admin's power level, and invites them if the room
+    /// isn't public; it doesn't join them. Rooms the admin has already joined are left as they
+    /// are, so this is safe to repeat.
+    pub async fn join_room_as_admin(&self, room_id: &RoomId) -> Result<()> {
+        let admin_id = self.whoami().await?;
+        self.join_room_as(room_id, &admin_id).await
+    }
+
+    /// `join_room_as_admin` for callers which already know the admin's ID.
+    pub(crate) async fn join_room_as(&self, room_id: &RoomId, admin_id: &UserId) -> Result<()> {
+        self.make_room_admin(room_id, None).await?;
+        let members = self.get_room_members(room_id).await?;
+        if members.members.iter().any(|member| member == admin_id) {
+            return Ok(());
+        }
+
+        // the admin `/join` endpoint would try to invite the admin on their own behalf, which
+        // fails when they aren't in the room yet, so accept `make_room_admin`'s invite instead
+        execute!(
+            self.inner
+                .post(endpoint!(client self format!("/rooms/{room_id}/join")))
+                .json(&serde_json::json!({}))
+                .send()
+                .await?
+                .json::<MatrixResult<EmptyObject>>()
+                .await?;
+            _r => ()
+        )
+    }
 }
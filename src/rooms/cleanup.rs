@@ -0,0 +1,112 @@
+use std::collections::HashSet;
+
+use futures_util::TryStreamExt;
+
+use crate::{prelude::*, SynapseError};
+
+use super::{
+    DeleteRoomBody, GetRoomsQuery, Room, RoomShutdownReport, ShutdownRoomsOptions,
+    TimestampToEventQuery,
+};
+
+#[derive(Debug, Clone, TypedBuilder)]
+pub struct EmptyRoomCleanup {
+    /// Only clean up rooms which have had no events since this time.
+    #[builder(default, setter(strip_option))]
+    pub inactive_since: Option<SystemTime>,
+    /// Rooms which are never cleaned up, even if they are empty.
+    #[builder(default)]
+    pub protected_rooms: HashSet<OwnedRoomId>,
+    /// Only find the rooms which would be cleaned up, without deleting them. Defaults to
+    /// `true`, so rooms are only deleted when asked for explicitly with `.dry_run(false)`.
+    #[builder(default = true)]
+    pub dry_run: bool,
+    #[builder(default)]
+    pub options: ShutdownRoomsOptions,
+}
+
+#[derive(Debug)]
+pub struct EmptyRoomCleanupReport {
+    /// The rooms which were (or, in a dry run, would have been) deleted.
+    pub rooms: Vec<Room>,
+    /// `None` if this was a dry run.
+    pub shutdown: Option<RoomShutdownReport>,
+}
+
+impl SynapseClient {
+    /// Find rooms with no joined local members which aren't protected by `cleanup`, and, if
+    /// `cleanup.inactive_since` is set, which have had no events since then.
+    pub async fn find_empty_rooms(&self, cleanup: &EmptyRoomCleanup) -> Result<Vec<Room>> {
+        let empty_rooms: Vec<Room> = self
+            .stream_rooms(GetRoomsQuery::builder().build())
+            .try_filter(|room| {
+                let is_candidate = room.joined_local_members == 0
+                    && !cleanup.protected_rooms.contains(&room.room_id);
+                async move { is_candidate }
+            })
+            .try_collect()
+            .await?;
+
+        let Some(inactive_since) = cleanup.inactive_since else {
+            return Ok(empty_rooms);
+        };
+
+        let query = TimestampToEventQuery::builder()
+            .timestamp(inactive_since)
+            .direction(SortDirection::Ascending)
+            .build();
+        let mut inactive_rooms = Vec::with_capacity(empty_rooms.len());
+        for room in empty_rooms {
+            let last_event = match self.timestamp_to_event(&room.room_id, &query).await {
+                Ok(event_id) => event_id,
+                // synapse responds with a 404 when there are no events after the timestamp
+                Err(SynapseError::Matrix(err)) if err.code == "M_NOT_FOUND" => None,
+                Err(err) => return Err(err),
+            };
+            if last_event.is_none() {
+                inactive_rooms.push(room);
+            }
+        }
+
+        Ok(inactive_rooms)
+    }
+
+    /// Delete and purge every room found by `find_empty_rooms`, unless `cleanup.dry_run` is
+    /// set, which it is by default.
+    ///
+    /// ```rs
+    /// let cleanup = EmptyRoomCleanup::builder()
+    ///     .inactive_since(SystemTime::now() - Duration::from_secs(90 * 24 * 60 * 60))
+    ///     .protected_rooms(HashSet::from([announcements_room_id]))
+    ///     .build();
+    /// let report = client.clean_up_empty_rooms(cleanup.clone()).await?;
+    /// for room in report.rooms {
+    ///     println!("would delete {}", room.room_id);
+    /// }
+    ///
+    /// let cleanup = EmptyRoomCleanup { dry_run: false, ..cleanup };
+    /// let report = client.clean_up_empty_rooms(cleanup).await?;
+    /// ```
+    pub async fn clean_up_empty_rooms(
+        &self,
+        cleanup: EmptyRoomCleanup,
+    ) -> Result<EmptyRoomCleanupReport> {
+        let rooms = self.find_empty_rooms(&cleanup).await?;
+
+        if cleanup.dry_run {
+            return Ok(EmptyRoomCleanupReport {
+                rooms,
+                shutdown: None,
+            });
+        }
+
+        let room_ids = rooms.iter().map(|room| room.room_id.clone());
+        let body = DeleteRoomBody::builder().purge(true).build();
+        let shutdown = self.shutdown_rooms(room_ids, body, cleanup.options).await;
+
+        Ok(EmptyRoomCleanupReport {
+            rooms,
+            shutdown: Some(shutdown),
+        })
+    }
+}
@@ -21,7 +21,7 @@ pub struct DeleteRoomBody<'a> {
 pub struct DeleteRoomV1Response {
     pub kicked_users: Vec<OwnedUserId>,
     pub failed_to_kick_users: Vec<OwnedUserId>,
-    pub local_aliases: Vec<OwnedRoomId>,
+    pub local_aliases: Vec<OwnedRoomAliasId>,
     pub new_room_id: Option<OwnedRoomId>,
 }
 
@@ -0,0 +1,77 @@
+pub use matrix_sdk::ruma::api::client::room::Visibility;
+
+use crate::prelude::*;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RoomVisibility {
+    visibility: Visibility,
+}
+
+impl SynapseClient {
+    /// Get whether the room is published to the server's public room directory.
+    pub async fn get_room_directory_visibility(&self, room_id: &RoomId) -> Result<Visibility> {
+        execute!(
+            self.inner
+                .get(endpoint!(client self format!("/directory/list/room/{room_id}")))
+                .send()
+                .await?
+                .json::<MatrixResult<RoomVisibility>>()
+                .await?;
+            res => res.visibility
+        )
+    }
+
+    /// Publish the room to (`Visibility::Public`) or remove it from (`Visibility::Private`)
+    /// the server's public room directory.
+    pub async fn set_room_directory_visibility(
+        &self,
+        room_id: &RoomId,
+        visibility: Visibility,
+    ) -> Result<()> {
+        execute!(
+            self.inner
+                .put(endpoint!(client self format!("/directory/list/room/{room_id}")))
+                .json(&RoomVisibility { visibility })
+                .send()
+                .await?
+                .json::<MatrixResult<EmptyObject>>()
+                .await?;
+            _r => ()
+        )
+    }
+
+    /// Get the local aliases which point to the room.
+    pub async fn get_room_aliases(&self, room_id: &RoomId) -> Result<Vec<OwnedRoomAliasId>> {
+        #[derive(Deserialize)]
+        struct Response {
+            aliases: Vec<OwnedRoomAliasId>,
+        }
+        execute!(
+            self.inner
+                .get(endpoint!(client self format!("/rooms/{room_id}/aliases")))
+                .send()
+                .await?
+                .json::<MatrixResult<Response>>()
+                .await?;
+            res => res.aliases
+        )
+    }
+
+    /// Delete a local room alias, regardless of who created it.
+    pub async fn delete_room_alias(&self, alias: &RoomAliasId) -> Result<()> {
+        // aliases may contain `#`, `?`, `/` or `%`, so the whole alias is percent-encoded
+        let mut url = endpoint!(client self "/directory/room");
+        url.path_segments_mut()
+            .expect("http URLs can have path segments")
+            .push(alias.as_str());
+        execute!(
+            self.inner
+                .delete(url)
+                .send()
+                .await?
+                .json::<MatrixResult<EmptyObject>>()
+                .await?;
+            _r => ()
+        )
+    }
+}
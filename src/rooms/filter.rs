@@ -0,0 +1,263 @@
+use futures_util::{future, Stream, TryStreamExt};
+use matrix_sdk::ruma::{api::client::space::SpaceRoomJoinRule, OwnedServerName, ServerName};
+
+use crate::prelude::*;
+
+use super::{GetRoomsQuery, Room};
+
+/// A set of conditions rooms must meet, used with `stream_rooms_filtered`.
+///
+/// Conditions which Synapse supports are sent along with the query, and every condition is
+/// checked again on the client, so filters work the same on Synapse versions which don't
+/// support them.
+///
+/// ```rs
+/// let filter = RoomFilter::new()
+///     .public_only()
+///     .unencrypted()
+///     .min_joined_members(10)
+///     .creator_domain(server_name!("spam.example"));
+/// let rooms: Vec<Room> = client
+///     .stream_rooms_filtered(GetRoomsQuery::builder().build(), filter)
+///     .try_collect()
+///     .await?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RoomFilter {
+    pub public: Option<bool>,
+    pub empty: Option<bool>,
+    pub encrypted: Option<bool>,
+    pub federatable: Option<bool>,
+    pub space: Option<bool>,
+    pub join_rule: Option<SpaceRoomJoinRule>,
+    pub min_joined_members: Option<usize>,
+    pub max_joined_members: Option<usize>,
+    pub creator_domain: Option<OwnedServerName>,
+}
+
+impl RoomFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn public(mut self, public: bool) -> Self {
+        self.public = Some(public);
+        self
+    }
+
+    pub fn public_only(self) -> Self {
+        self.public(true)
+    }
+
+    /// Match rooms with no joined members (`true`) or with at least one (`false`).
+    pub fn empty(mut self, empty: bool) -> Self {
+        self.empty = Some(empty);
+        self
+    }
+
+    pub fn encrypted(mut self, encrypted: bool) -> Self {
+        self.encrypted = Some(encrypted);
+        self
+    }
+
+    pub fn unencrypted(self) -> Self {
+        self.encrypted(false)
+    }
+
+    pub fn federatable(mut self, federatable: bool) -> Self {
+        self.federatable = Some(federatable);
+        self
+    }
+
+    /// Match spaces (`true`) or regular rooms (`false`).
+    pub fn space(mut self, space: bool) -> Self {
+        self.space = Some(space);
+        self
+    }
+
+    pub fn space_only(self) -> Self {
+        self.space(true)
+    }
+
+    pub fn join_rule(mut self, join_rule: SpaceRoomJoinRule) -> Self {
+        self.join_rule = Some(join_rule);
+        self
+    }
+
+    pub fn min_joined_members(mut self, min: usize) -> Self {
+        self.min_joined_members = Some(min);
+        self
+    }
+
+    pub fn max_joined_members(mut self, max: usize) -> Self {
+        self.max_joined_members = Some(max);
+        self
+    }
+
+    /// Match rooms created by a user on `server_name`.
+    pub fn creator_domain(mut self, server_name: &ServerName) -> Self {
+        self.creator_domain = Some(server_name.to_owned());
+        self
+    }
+
+    /// Copy the conditions Synapse can filter by into `query`.
+    pub fn apply_to_query(&self, query: &mut GetRoomsQuery) {
+        if self.public.is_some() {
+            query.public_rooms = self.public;
+        }
+        if self.empty.is_some() {
+            query.empty_rooms = self.empty;
+        }
+    }
+
+    pub fn matches(&self, room: &Room) -> bool {
+        fn check<T>(condition: &Option<T>, f: impl FnOnce(&T) -> bool) -> bool {
+            condition.as_ref().is_none_or(f)
+        }
+
+        check(&self.public, |&public| room.public == public)
+            && check(&self.empty, |&empty| (room.joined_members == 0) == empty)
+            && check(&self.encrypted, |&encrypted| {
+                room.encryption.is_some() == encrypted
+            })
+            && check(&self.federatable, |&federatable| {
+                room.federatable == federatable
+            })
+            && check(&self.space, |&space| {
+                (room.room_type.as_deref() == Some("m.space")) == space
+            })
+            && check(&self.join_rule, |join_rule| {
+                room.join_rules.as_ref() == Some(join_rule)
+            })
+            && check(&self.min_joined_members, |&min| room.joined_members >= min)
+            && check(&self.max_joined_members, |&max| room.joined_members <= max)
+            && check(&self.creator_domain, |domain| {
+                room.creator.server_name() == domain
+            })
+    }
+}
+
+impl SynapseClient {
+    /// Stream every room matching both `query` and `filter`.
+    pub fn stream_rooms_filtered(
+        &self,
+        mut query: GetRoomsQuery,
+        filter: RoomFilter,
+    ) -> impl Stream<Item = Result<Room>> + '_ {
+        filter.apply_to_query(&mut query);
+        self.stream_rooms(query)
+            .try_filter(move |room| future::ready(filter.matches(room)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn room(room_id: &str, extra: serde_json::Value) -> Room {
+        let mut room = serde_json::json!({
+            "room_id": room_id,
+            "name": null,
+            "canonical_alias": null,
+            "joined_members": 3,
+            "joined_local_members": 2,
+            "version": "10",
+            "creator": "@alice:example.com",
+            "encryption": null,
+            "federatable": true,
+            "public": false,
+            "join_rules": "invite",
+            "guest_access": null,
+            "history_visibility": "shared",
+            "state_events": 12,
+            "room_type": null
+        });
+        room.as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        serde_json::from_value(room).unwrap()
+    }
+
+    fn rooms() -> Vec<Room> {
+        vec![
+            room("!private:example.com", serde_json::json!({})),
+            room(
+                "!encrypted:example.com",
+                serde_json::json!({ "encryption": "m.megolm.v1.aes-sha2" }),
+            ),
+            room(
+                "!public:example.com",
+                serde_json::json!({ "public": true, "join_rules": "public" }),
+            ),
+            room(
+                "!space:example.com",
+                serde_json::json!({
+                    "public": true,
+                    "join_rules": "public",
+                    "room_type": "m.space"
+                }),
+            ),
+            room(
+                "!restricted:example.com",
+                serde_json::json!({ "join_rules": "restricted", "creator": "@spam:spam.example" }),
+            ),
+        ]
+    }
+
+    fn matching(filter: RoomFilter) -> Vec<String> {
+        rooms()
+            .into_iter()
+            .filter(|room| filter.matches(room))
+            .map(|room| room.room_id.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn empty_filter_matches_everything() {
+        assert_eq!(matching(RoomFilter::new()).len(), 5);
+    }
+
+    #[test]
+    fn filters_by_join_rule() {
+        assert_eq!(
+            matching(RoomFilter::new().join_rule(SpaceRoomJoinRule::Public)),
+            ["!public:example.com", "!space:example.com"]
+        );
+        assert_eq!(
+            matching(RoomFilter::new().join_rule(SpaceRoomJoinRule::Restricted)),
+            ["!restricted:example.com"]
+        );
+    }
+
+    #[test]
+    fn filters_by_room_type() {
+        assert_eq!(
+            matching(RoomFilter::new().space_only()),
+            ["!space:example.com"]
+        );
+        assert_eq!(
+            matching(RoomFilter::new().public_only().space(false)),
+            ["!public:example.com"]
+        );
+    }
+
+    #[test]
+    fn filters_by_encryption() {
+        assert_eq!(
+            matching(RoomFilter::new().encrypted(true)),
+            ["!encrypted:example.com"]
+        );
+        assert_eq!(matching(RoomFilter::new().unencrypted()).len(), 4);
+    }
+
+    #[test]
+    fn combines_conditions() {
+        let spam = ServerName::parse("spam.example").unwrap();
+        assert_eq!(
+            matching(RoomFilter::new().unencrypted().creator_domain(&spam)),
+            ["!restricted:example.com"]
+        );
+        assert!(matching(RoomFilter::new().public_only().creator_domain(&spam)).is_empty());
+        assert!(matching(RoomFilter::new().min_joined_members(4)).is_empty());
+    }
+}
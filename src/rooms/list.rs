@@ -1,8 +1,10 @@
-use matrix_sdk::ruma::events::room::{
-    guest_access::GuestAccess, history_visibility::HistoryVisibility, join_rules::JoinRule,
+use futures_util::Stream;
+use matrix_sdk::ruma::{
+    api::client::space::SpaceRoomJoinRule,
+    events::room::{guest_access::GuestAccess, history_visibility::HistoryVisibility},
 };
 
-use crate::prelude::*;
+use crate::{pagination::paginate, prelude::*};
 
 #[skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Deserialize, TypedBuilder)]
@@ -18,6 +20,14 @@ pub struct GetRoomsQuery {
     pub direction: Option<SortDirection>,
     #[builder(default, setter(strip_option))]
     pub search_term: Option<String>,
+    /// Only return public (`true`) or non-public (`false`) rooms. Ignored by older Synapse
+    /// versions, see `RoomFilter` for a filter which also works on those.
+    #[builder(default, setter(strip_option))]
+    pub public_rooms: Option<bool>,
+    /// Only return rooms with no joined members (`true`) or with at least one (`false`).
+    /// Ignored by older Synapse versions.
+    #[builder(default, setter(strip_option))]
+    pub empty_rooms: Option<bool>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -50,7 +60,7 @@ pub struct RoomsQueryResponse {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Room {
     pub room_id: OwnedRoomId,
-    pub name: String,
+    pub name: Option<String>,
     pub canonical_alias: Option<String>,
     pub joined_members: usize,
     pub joined_local_members: usize,
@@ -59,7 +69,9 @@ pub struct Room {
     pub encryption: Option<String>,
     pub federatable: bool,
     pub public: bool,
-    pub join_rules: JoinRule,
+    /// This is a plain join rule; the conditions of restricted join rules are only available in
+    /// the room's `m.room.join_rules` state event.
+    pub join_rules: Option<SpaceRoomJoinRule>,
     pub guest_access: Option<GuestAccess>,
     pub history_visibility: HistoryVisibility,
     pub state_events: usize,
@@ -78,4 +90,16 @@ impl SynapseClient {
                 .await?
         )
     }
+
+    /// Stream every room matching `query`, following `next_batch` until the last page.
+    pub fn stream_rooms(&self, query: GetRoomsQuery) -> impl Stream<Item = Result<Room>> + '_ {
+        paginate(query, move |query: GetRoomsQuery| async move {
+            let page = self.get_rooms(query.clone()).await?;
+            let next = page.next_batch.map(|from| GetRoomsQuery {
+                from: Some(from),
+                ..query
+            });
+            Ok((page.rooms, next))
+        })
+    }
 }
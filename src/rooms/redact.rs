@@ -0,0 +1,38 @@
+use matrix_sdk::ruma::TransactionId;
+
+use crate::prelude::*;
+
+impl SynapseClient {
+    /// Redact an event as the user who owns this client's access token. Returns the ID of the
+    /// redaction event.
+    ///
+    /// The admin user must be joined to the room with a high enough power level to redact
+    /// other users' events, which `join_room_as_admin` can be used to arrange.
+    pub async fn redact_event(
+        &self,
+        room_id: &RoomId,
+        event_id: &EventId,
+        reason: Option<&str>,
+    ) -> Result<OwnedEventId> {
+        #[skip_serializing_none]
+        #[derive(Serialize)]
+        struct Body<'a> {
+            reason: Option<&'a str>,
+        }
+        #[derive(Deserialize)]
+        struct Response {
+            event_id: OwnedEventId,
+        }
+        let txn_id = TransactionId::new();
+        execute!(
+            self.inner
+                .put(endpoint!(client self format!("/rooms/{room_id}/redact/{event_id}/{txn_id}")))
+                .json(&Body { reason })
+                .send()
+                .await?
+                .json::<MatrixResult<Response>>()
+                .await?;
+            res => res.event_id
+        )
+    }
+}
@@ -1,5 +1,6 @@
-use matrix_sdk::ruma::events::room::{
-    guest_access::GuestAccess, history_visibility::HistoryVisibility, join_rules::JoinRule,
+use matrix_sdk::ruma::{
+    api::client::space::SpaceRoomJoinRule,
+    events::room::{guest_access::GuestAccess, history_visibility::HistoryVisibility},
 };
 
 use crate::prelude::*;
@@ -7,7 +8,7 @@ use crate::prelude::*;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RoomDetails {
     pub room_id: OwnedRoomId,
-    pub name: String,
+    pub name: Option<String>,
     pub topic: Option<String>,
     pub avatar: Option<String>,
     pub canonical_alias: Option<String>,
@@ -19,7 +20,9 @@ pub struct RoomDetails {
     pub encryption: Option<String>,
     pub federatable: bool,
     pub public: bool,
-    pub join_rules: JoinRule,
+    /// This is a plain join rule; the conditions of restricted join rules are only available in
+    /// the room's `m.room.join_rules` state event.
+    pub join_rules: Option<SpaceRoomJoinRule>,
     pub guest_access: Option<GuestAccess>,
     pub history_visibility: HistoryVisibility,
     pub state_events: usize,
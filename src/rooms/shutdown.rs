@@ -0,0 +1,191 @@
+use std::time::Duration;
+
+use futures_util::{stream, StreamExt, TryStreamExt};
+
+use crate::{polling::wait_for_purge, prelude::*};
+
+use super::{DeleteRoomBody, DeleteRoomPurgeStatus, DeleteRoomV1Response, GetRoomsQuery, Room};
+
+#[derive(Debug, Clone, TypedBuilder)]
+pub struct ShutdownRoomsOptions {
+    /// The maximum number of rooms being deleted at the same time.
+    #[builder(default = 4)]
+    pub concurrency: usize,
+    /// How long to wait between checks of a deletion's status.
+    #[builder(default = Duration::from_secs(1))]
+    pub poll_interval: Duration,
+    /// How long to wait for each deletion to finish before giving up on tracking it.
+    #[builder(default = Duration::from_secs(60 * 60))]
+    pub timeout: Duration,
+}
+
+impl Default for ShutdownRoomsOptions {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+#[derive(Debug)]
+pub struct RoomShutdownOutcome {
+    pub room_id: OwnedRoomId,
+    /// `None` if the deletion could not be started.
+    pub delete_id: Option<String>,
+    /// The final status of the deletion, or the error which stopped it from being tracked.
+    pub result: Result<DeleteRoomPurgeStatus>,
+}
+
+impl RoomShutdownOutcome {
+    /// Whether the room was shut down and purged without any errors.
+    pub fn is_complete(&self) -> bool {
+        matches!(
+            self.result,
+            Ok(DeleteRoomPurgeStatus {
+                status: PurgeStatus::Complete,
+                ..
+            })
+        )
+    }
+
+    /// The users kicked, aliases removed and replacement room created for this room, if any.
+    pub fn shutdown_room(&self) -> Option<&DeleteRoomV1Response> {
+        self.result
+            .as_ref()
+            .ok()
+            .and_then(|status| status.shutdown_room.as_ref())
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct RoomShutdownReport {
+    pub rooms: Vec<RoomShutdownOutcome>,
+}
+
+impl RoomShutdownReport {
+    /// Outcomes for rooms which failed to start, failed to be tracked, or failed to purge.
+    pub fn failures(&self) -> impl Iterator<Item = &RoomShutdownOutcome> {
+        self.rooms.iter().filter(|outcome| !outcome.is_complete())
+    }
+
+    fn shutdown_rooms(&self) -> impl Iterator<Item = &DeleteRoomV1Response> {
+        self.rooms
+            .iter()
+            .filter_map(RoomShutdownOutcome::shutdown_room)
+    }
+
+    pub fn kicked_users(&self) -> impl Iterator<Item = &OwnedUserId> {
+        self.shutdown_rooms().flat_map(|room| &room.kicked_users)
+    }
+
+    pub fn failed_to_kick_users(&self) -> impl Iterator<Item = &OwnedUserId> {
+        self.shutdown_rooms()
+            .flat_map(|room| &room.failed_to_kick_users)
+    }
+
+    pub fn local_aliases(&self) -> impl Iterator<Item = &OwnedRoomAliasId> {
+        self.shutdown_rooms().flat_map(|room| &room.local_aliases)
+    }
+
+    pub fn new_room_ids(&self) -> impl Iterator<Item = &OwnedRoomId> {
+        self.shutdown_rooms()
+            .filter_map(|room| room.new_room_id.as_ref())
+    }
+}
+
+impl SynapseClient {
+    /// Delete every room in `room_ids` with `delete_room_v2`, waiting for each deletion to finish.
+    ///
+    /// At most `options.concurrency` rooms are deleted at once. Errors are recorded per room in
+    /// the report rather than aborting the whole batch.
+    ///
+    /// ```rs
+    /// let body = DeleteRoomBody::builder().block(true).purge(true).build();
+    /// let report = client
+    ///     .shutdown_rooms(room_ids, body, ShutdownRoomsOptions::default())
+    ///     .await;
+    /// for failure in report.failures() {
+    ///     eprintln!("{}: {:?}", failure.room_id, failure.result);
+    /// }
+    /// ```
+    pub async fn shutdown_rooms(
+        &self,
+        room_ids: impl IntoIterator<Item = OwnedRoomId>,
+        body: DeleteRoomBody<'_>,
+        options: ShutdownRoomsOptions,
+    ) -> RoomShutdownReport {
+        let rooms = stream::iter(room_ids)
+            .map(|room_id| self.shutdown_room(room_id, body.clone(), &options))
+            .buffer_unordered(options.concurrency.max(1))
+            .collect()
+            .await;
+
+        RoomShutdownReport { rooms }
+    }
+
+    /// Search for rooms with `query`, then delete every room for which `predicate` returns `true`.
+    ///
+    /// See `shutdown_rooms` for how the deletions are run.
+    pub async fn shutdown_rooms_where(
+        &self,
+        query: GetRoomsQuery,
+        predicate: impl Fn(&Room) -> bool,
+        body: DeleteRoomBody<'_>,
+        options: ShutdownRoomsOptions,
+    ) -> Result<RoomShutdownReport> {
+        let room_ids: Vec<OwnedRoomId> = self
+            .stream_rooms(query)
+            .try_filter_map(|room| {
+                let room_id = predicate(&room).then_some(room.room_id);
+                async move { Ok(room_id) }
+            })
+            .try_collect()
+            .await?;
+
+        Ok(self.shutdown_rooms(room_ids, body, options).await)
+    }
+
+    async fn shutdown_room(
+        &self,
+        room_id: OwnedRoomId,
+        body: DeleteRoomBody<'_>,
+        options: &ShutdownRoomsOptions,
+    ) -> RoomShutdownOutcome {
+        let delete_id = match self.delete_room_v2(&room_id, body).await {
+            Ok(delete_id) => delete_id,
+            Err(err) => {
+                return RoomShutdownOutcome {
+                    room_id,
+                    delete_id: None,
+                    result: Err(err),
+                }
+            }
+        };
+
+        let result = self
+            .wait_for_room_delete(&delete_id, options.poll_interval, options.timeout)
+            .await;
+
+        RoomShutdownOutcome {
+            room_id,
+            delete_id: Some(delete_id),
+            result,
+        }
+    }
+
+    /// Poll `get_delete_status_by_id` until the deletion has either completed or failed, or
+    /// until `timeout` has passed.
+    pub async fn wait_for_room_delete(
+        &self,
+        delete_id: &str,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<DeleteRoomPurgeStatus> {
+        wait_for_purge(
+            delete_id,
+            poll_interval,
+            timeout,
+            || self.get_delete_status_by_id(delete_id),
+            |status: &DeleteRoomPurgeStatus| status.status,
+        )
+        .await
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::HashSet;
+
+use futures_util::{future::BoxFuture, FutureExt};
+use matrix_sdk::ruma::events::AnyStateEvent;
+
+use crate::{prelude::*, room_membership::RoomMembershipReport, SynapseError};
+
+use super::{DeleteRoomBody, RoomDetails, RoomShutdownReport, ShutdownRoomsOptions};
+
+/// A room in a space hierarchy, with the rooms and subspaces in it if it is a space.
+#[derive(Debug, Clone)]
+pub struct SpaceNode {
+    pub room_id: OwnedRoomId,
+    /// `None` if the server doesn't know the room, such as a room on another server which no
+    /// local user has joined.
+    pub details: Option<RoomDetails>,
+    pub children: Vec<SpaceNode>,
+}
+
+impl SpaceNode {
+    pub fn is_space(&self) -> bool {
+        self.details
+            .as_ref()
+            .is_some_and(|details| details.room_type.as_deref() == Some("m.space"))
+    }
+
+    /// This room and every room below it, depth-first.
+    pub fn iter(&self) -> impl Iterator<Item = &SpaceNode> {
+        let mut stack = vec![self];
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.children.iter().rev());
+            Some(node)
+        })
+    }
+
+    pub fn room_ids(&self) -> impl Iterator<Item = &OwnedRoomId> {
+        self.iter().map(|node| &node.room_id)
+    }
+
+    /// The rooms in this subtree which the server knows about.
+    pub fn known_room_ids(&self) -> impl Iterator<Item = &OwnedRoomId> {
+        self.iter()
+            .filter(|node| node.details.is_some())
+            .map(|node| &node.room_id)
+    }
+}
+
+#[derive(Debug)]
+pub struct SpaceOperationOutcome<T> {
+    pub room_id: OwnedRoomId,
+    pub result: Result<T>,
+}
+
+#[derive(Debug)]
+pub struct SpaceOperationReport<T> {
+    pub rooms: Vec<SpaceOperationOutcome<T>>,
+}
+
+impl<T> SpaceOperationReport<T> {
+    pub fn failures(&self) -> impl Iterator<Item = &SpaceOperationOutcome<T>> {
+        self.rooms.iter().filter(|room| room.result.is_err())
+    }
+}
+
+impl SynapseClient {
+    /// List the rooms and subspaces directly in a space, according to its `m.space.child`
+    /// state. Children which were removed from the space are skipped.
+    pub async fn get_space_children(&self, space_id: &RoomId) -> Result<Vec<OwnedRoomId>> {
+        let children = self
+            .get_room_state(space_id)
+            .await?
+            .into_iter()
+            .filter_map(|event| match event {
+                AnyStateEvent::SpaceChild(event) => {
+                    let event = event.as_original()?;
+                    // removing a child is done by sending the event without `via`
+                    let via = event.content.via.as_ref()?;
+                    (!via.is_empty()).then(|| event.state_key.clone())
+                }
+                _ => None,
+            })
+            .collect();
+        Ok(children)
+    }
+
+    /// Walk a space and its subspaces, getting the details of every room in them.
+    ///
+    /// Only spaces known to the server can be walked. A room which is in several spaces of the
+    /// hierarchy only appears under the first one, so cycles between spaces are cut.
+    ///
+    /// ```rs
+    /// let tree = client.get_space_hierarchy(&space_id).await?;
+    /// for node in tree.iter() {
+    ///     let name = node.details.as_ref().and_then(|details| details.name.as_deref());
+    ///     println!("{} {:?}", node.room_id, name);
+    /// }
+    /// let report = client.block_space_tree(&tree).await;
+    /// ```
+    pub async fn get_space_hierarchy(&self, space_id: &RoomId) -> Result<SpaceNode> {
+        let mut visited = HashSet::new();
+        self.walk_space(space_id.to_owned(), &mut visited).await
+    }
+
+    fn walk_space<'a>(
+        &'a self,
+        room_id: OwnedRoomId,
+        visited: &'a mut HashSet<OwnedRoomId>,
+    ) -> BoxFuture<'a, Result<SpaceNode>> {
+        async move {
+            visited.insert(room_id.clone());
+            let details = match self.get_room(&room_id).await {
+                Ok(details) => Some(details),
+                Err(SynapseError::Matrix(err)) if err.code == "M_NOT_FOUND" => None,
+                Err(err) => return Err(err),
+            };
+
+            let mut node = SpaceNode {
+                room_id,
+                details,
+                children: Vec::new(),
+            };
+            if node.is_space() {
+                for child in self.get_space_children(&node.room_id).await? {
+                    if !visited.contains(&child) {
+                        node.children.push(self.walk_space(child, visited).await?);
+                    }
+                }
+            }
+            Ok(node)
+        }
+        .boxed()
+    }
+
+    /// Block every room in the subtree, including rooms the server doesn't know yet, so local
+    /// users can't join them.
+    pub async fn block_space_tree(&self, tree: &SpaceNode) -> SpaceOperationReport<bool> {
+        let mut rooms = Vec::new();
+        for room_id in tree.room_ids() {
+            rooms.push(SpaceOperationOutcome {
+                room_id: room_id.clone(),
+                result: self.set_room_blocked(room_id, true).await,
+            });
+        }
+        SpaceOperationReport { rooms }
+    }
+
+    /// Delete every room in the subtree which the server knows about. See `shutdown_rooms`.
+    pub async fn delete_space_tree(
+        &self,
+        tree: &SpaceNode,
+        body: DeleteRoomBody<'_>,
+        options: ShutdownRoomsOptions,
+    ) -> RoomShutdownReport {
+        self.shutdown_rooms(tree.known_room_ids().cloned(), body, options)
+            .await
+    }
+
+    /// Join a local user to every room in the subtree. See `add_user_to_rooms`.
+    pub async fn add_user_to_space_tree(
+        &self,
+        user_id: &UserId,
+        tree: &SpaceNode,
+    ) -> RoomMembershipReport {
+        self.add_user_to_rooms(user_id, tree.room_ids().cloned())
+            .await
+    }
+
+    /// Quarantine the media in every room in the subtree which the server knows about,
+    /// reporting the number of media items quarantined in each room.
+    pub async fn quarantine_space_tree_media(
+        &self,
+        tree: &SpaceNode,
+    ) -> SpaceOperationReport<usize> {
+        let mut rooms = Vec::new();
+        for room_id in tree.known_room_ids() {
+            rooms.push(SpaceOperationOutcome {
+                room_id: room_id.clone(),
+                result: self.quarantine_all_media_in_room(room_id).await,
+            });
+        }
+        SpaceOperationReport { rooms }
+    }
+}
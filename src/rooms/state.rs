@@ -1,4 +1,13 @@
-use matrix_sdk::ruma::events::AnyStateEvent;
+use matrix_sdk::ruma::events::{
+    room::{
+        history_visibility::{HistoryVisibility, RoomHistoryVisibilityEventContent},
+        join_rules::{JoinRule, RoomJoinRulesEventContent},
+        name::RoomNameEventContent,
+        power_levels::RoomPowerLevelsEventContent,
+        topic::RoomTopicEventContent,
+    },
+    AnyStateEvent, EmptyStateKey, StateEventContent,
+};
 
 use crate::prelude::*;
 
@@ -18,4 +27,92 @@ impl SynapseClient {
             res => res.state
         )
     }
+
+    /// Send a state event into the room as the user who owns this client's access token.
+    ///
+    /// The admin user must be joined to the room with a high enough power level to send the
+    /// event, which `join_room_as_admin` can be used to arrange.
+    ///
+    /// ```rs
+    /// client.join_room_as_admin(&room_id).await?;
+    /// client.set_room_join_rule(&room_id, JoinRule::Invite).await?;
+    /// client
+    ///     .set_room_history_visibility(&room_id, HistoryVisibility::Joined)
+    ///     .await?;
+    /// ```
+    pub async fn send_state_event<C: StateEventContent>(
+        &self,
+        room_id: &RoomId,
+        content: &C,
+        state_key: &C::StateKey,
+    ) -> Result<OwnedEventId> {
+        #[derive(Deserialize)]
+        struct Response {
+            event_id: OwnedEventId,
+        }
+        execute!(
+            self.inner
+                .put(endpoint!(client self format!(
+                    "/rooms/{room_id}/state/{}/{}",
+                    content.event_type(),
+                    state_key.as_ref()
+                )))
+                .json(content)
+                .send()
+                .await?
+                .json::<MatrixResult<Response>>()
+                .await?;
+            res => res.event_id
+        )
+    }
+
+    pub async fn set_room_power_levels(
+        &self,
+        room_id: &RoomId,
+        power_levels: &RoomPowerLevelsEventContent,
+    ) -> Result<OwnedEventId> {
+        self.send_state_event(room_id, power_levels, &EmptyStateKey)
+            .await
+    }
+
+    pub async fn set_room_join_rule(
+        &self,
+        room_id: &RoomId,
+        join_rule: JoinRule,
+    ) -> Result<OwnedEventId> {
+        self.send_state_event(
+            room_id,
+            &RoomJoinRulesEventContent::new(join_rule),
+            &EmptyStateKey,
+        )
+        .await
+    }
+
+    pub async fn set_room_history_visibility(
+        &self,
+        room_id: &RoomId,
+        history_visibility: HistoryVisibility,
+    ) -> Result<OwnedEventId> {
+        self.send_state_event(
+            room_id,
+            &RoomHistoryVisibilityEventContent::new(history_visibility),
+            &EmptyStateKey,
+        )
+        .await
+    }
+
+    /// Set the name of the room, or remove it if `name` is `None`.
+    pub async fn set_room_name(
+        &self,
+        room_id: &RoomId,
+        name: Option<String>,
+    ) -> Result<OwnedEventId> {
+        self.send_state_event(room_id, &RoomNameEventContent::new(name), &EmptyStateKey)
+            .await
+    }
+
+    pub async fn set_room_topic(&self, room_id: &RoomId, topic: String) -> Result<OwnedEventId> {
+        self.send_state_event(room_id, &RoomTopicEventContent::new(topic), &EmptyStateKey)
+            .await
+    }
 }
@@ -4,15 +4,40 @@ use serde::{Deserialize, Serialize};
 
 use crate::prelude::*;
 
+mod broadcast;
+pub use broadcast::*;
+
+mod content;
+pub use content::*;
+
+mod template;
+pub use template::*;
+
+#[skip_serializing_none]
 #[derive(Debug, Clone, Serialize, TypedBuilder)]
 pub struct ServerNoticeBody<'a, T: Serialize + Debug> {
     pub user_id: &'a UserId,
     pub content: T,
+    /// The event type of the notice, defaults to `m.room.message`.
     #[serde(rename = "type")]
+    #[builder(default, setter(strip_option))]
     pub notice_type: Option<&'a str>,
+    /// Setting this sends the notice as a state event.
+    #[builder(default, setter(strip_option))]
     pub state_key: Option<&'a str>,
 }
 
+impl<'a, T: Serialize + Debug> ServerNoticeBody<'a, T> {
+    pub fn new(user_id: &'a UserId, content: T) -> Self {
+        Self {
+            user_id,
+            content,
+            notice_type: None,
+            state_key: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct MessageContent<'a> {
     pub body: &'a str,
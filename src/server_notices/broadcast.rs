@@ -0,0 +1,146 @@
+use std::{collections::HashSet, fmt::Debug, time::Duration};
+
+use futures_util::{stream, StreamExt, TryStreamExt};
+use serde::Serialize;
+
+use crate::{prelude::*, users::GetUsersQuery, SynapseError};
+
+use super::ServerNoticeBody;
+
+#[derive(Debug, Clone, TypedBuilder)]
+pub struct BroadcastOptions {
+    /// Prefix of the transaction ID of each notice. Reuse the same prefix when resuming an
+    /// interrupted broadcast so users who already received the notice don't get it twice.
+    #[builder(setter(into))]
+    pub txn_prefix: String,
+    /// Users who already received the notice, e.g. from [`BroadcastReport::sent_user_ids`] of an
+    /// earlier run. They are skipped.
+    #[builder(default)]
+    pub already_sent: HashSet<OwnedUserId>,
+    /// The maximum number of notices being sent at the same time.
+    #[builder(default = 4)]
+    pub concurrency: usize,
+    /// How many times to retry a notice after being rate limited.
+    #[builder(default = 5)]
+    pub max_retries: usize,
+    /// How long to wait after being rate limited if Synapse doesn't say.
+    #[builder(default = Duration::from_secs(1))]
+    pub default_retry_after: Duration,
+}
+
+#[derive(Debug, Default)]
+pub struct BroadcastReport {
+    pub sent: Vec<(OwnedUserId, OwnedEventId)>,
+    pub failed: Vec<(OwnedUserId, SynapseError)>,
+}
+
+impl BroadcastReport {
+    /// The users who received the notice, to pass as [`BroadcastOptions::already_sent`] when
+    /// resuming the broadcast.
+    pub fn sent_user_ids(&self) -> HashSet<OwnedUserId> {
+        self.sent
+            .iter()
+            .map(|(user_id, _)| user_id.clone())
+            .collect()
+    }
+}
+
+impl SynapseClient {
+    /// Send a server notice to every local user who isn't deactivated or a guest.
+    ///
+    /// Each notice is sent with `update_server_notice` using a transaction ID derived from
+    /// `options.txn_prefix` and the user's ID, so re-running an interrupted broadcast with the
+    /// same prefix soon after won't send duplicates. Synapse only remembers transaction IDs for
+    /// about 30 minutes, so to resume later keep the report of each run and pass the users who
+    /// were sent the notice as `options.already_sent`.
+    ///
+    /// ```rs
+    /// let content = MessageContent {
+    ///     body: "The server will be down for maintenance on Sunday.",
+    ///     msgtype: "m.notice",
+    /// };
+    /// let options = BroadcastOptions::builder()
+    ///     .txn_prefix("maintenance-2023-03-19")
+    ///     .build();
+    /// let report = client.broadcast_server_notice(&content, options).await?;
+    /// for (user_id, err) in &report.failed {
+    ///     eprintln!("{user_id}: {err}");
+    /// }
+    ///
+    /// // later, retry the users who failed
+    /// let options = BroadcastOptions::builder()
+    ///     .txn_prefix("maintenance-2023-03-19")
+    ///     .already_sent(report.sent_user_ids())
+    ///     .build();
+    /// client.broadcast_server_notice(&content, options).await?;
+    /// ```
+    pub async fn broadcast_server_notice<T: Serialize + Debug + Sync>(
+        &self,
+        content: &T,
+        options: BroadcastOptions,
+    ) -> Result<BroadcastReport> {
+        let query = GetUsersQuery::builder()
+            .guests(false)
+            .deactivated(false)
+            .build();
+        let user_ids: Vec<OwnedUserId> = self
+            .stream_users(query)
+            .map_ok(|user| user.name)
+            .try_filter(|user_id| std::future::ready(!options.already_sent.contains(user_id)))
+            .try_collect()
+            .await?;
+
+        let options = &options;
+        let results: Vec<_> = stream::iter(user_ids)
+            .map(|user_id| async move {
+                let result = self.send_broadcast_notice(&user_id, content, options).await;
+                (user_id, result)
+            })
+            .buffer_unordered(options.concurrency.max(1))
+            .collect()
+            .await;
+
+        let mut report = BroadcastReport::default();
+        for (user_id, result) in results {
+            match result {
+                Ok(event_id) => report.sent.push((user_id, event_id)),
+                Err(err) => report.failed.push((user_id, err)),
+            }
+        }
+        Ok(report)
+    }
+
+    async fn send_broadcast_notice<T: Serialize + Debug>(
+        &self,
+        user_id: &UserId,
+        content: &T,
+        options: &BroadcastOptions,
+    ) -> Result<OwnedEventId> {
+        // hex encoding keeps the transaction ID unique per user and safe to put in a URL
+        let txn_id = user_id.as_bytes().iter().fold(
+            format!("{}-", options.txn_prefix),
+            |mut txn_id, byte| {
+                txn_id.push_str(&format!("{byte:02x}"));
+                txn_id
+            },
+        );
+
+        let mut retries = 0;
+        loop {
+            let notice = ServerNoticeBody::new(user_id, content);
+            match self.update_server_notice(&txn_id, notice).await {
+                Err(SynapseError::Matrix(err))
+                    if err.code == "M_LIMIT_EXCEEDED" && retries < options.max_retries =>
+                {
+                    retries += 1;
+                    let retry_after = err
+                        .retry_after_ms
+                        .map(Duration::from_millis)
+                        .unwrap_or(options.default_retry_after);
+                    tokio::time::sleep(retry_after).await;
+                }
+                result => return result,
+            }
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use matrix_sdk::ruma::events::room::{
+    message::{ImageMessageEventContent, MessageType, RoomMessageEventContent},
+    ImageInfo,
+};
+
+use crate::prelude::*;
+
+use super::ServerNoticeBody;
+
+/// A server notice with formatted text or an attachment, which clients like Element can render.
+///
+/// ```rs
+/// let notice = RichNotice::markdown(&user_id, "Your account will expire **tomorrow**.");
+/// client.send_server_notice(notice).await?;
+/// ```
+pub type RichNotice<'a> = ServerNoticeBody<'a, RoomMessageEventContent>;
+
+impl<'a> RichNotice<'a> {
+    pub fn plain(user_id: &'a UserId, body: impl Into<String>) -> Self {
+        Self::new(user_id, RoomMessageEventContent::notice_plain(body))
+    }
+
+    /// `body` is shown by clients which can't render `html_body`.
+    pub fn html(
+        user_id: &'a UserId,
+        body: impl Into<String>,
+        html_body: impl Into<String>,
+    ) -> Self {
+        Self::new(
+            user_id,
+            RoomMessageEventContent::notice_html(body, html_body),
+        )
+    }
+
+    /// Render `markdown` to HTML, keeping the markdown source as the plain text body.
+    pub fn markdown(user_id: &'a UserId, markdown: impl AsRef<str> + Into<String>) -> Self {
+        Self::new(user_id, RoomMessageEventContent::notice_markdown(markdown))
+    }
+
+    /// Send an image which has already been uploaded to `url`. `body` is used as the image's
+    /// description.
+    pub fn image(
+        user_id: &'a UserId,
+        body: impl Into<String>,
+        url: OwnedMxcUri,
+        info: Option<ImageInfo>,
+    ) -> Self {
+        let image = ImageMessageEventContent::plain(body.into(), url, info.map(Box::new));
+        Self::new(
+            user_id,
+            RoomMessageEventContent::new(MessageType::Image(image)),
+        )
+    }
+}
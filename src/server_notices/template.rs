@@ -0,0 +1,289 @@
+use std::collections::HashMap;
+
+use matrix_sdk::ruma::events::room::message::FormattedBody;
+
+use crate::{dates::format_date, prelude::*, SynapseError};
+
+use super::RichNotice;
+
+/// A markdown server notice with `{variable}` placeholders, translated into several locales.
+/// Write `{{` and `}}` for literal braces.
+///
+/// ```rs
+/// let template = NoticeTemplate::new(
+///     "en",
+///     "Hi {displayname}, your account expires on **{expiry_date}**.",
+/// )
+/// .translation("de", "Hallo {displayname}, dein Konto läuft am **{expiry_date}** ab.");
+/// let vars = NoticeVariables::new().date("expiry_date", expiration_ts);
+/// let locales = |user_id: &UserId| user_locales.get(user_id).cloned();
+///
+/// let preview = client
+///     .render_templated_notice(&user_id, &template, &vars, locales)
+///     .await?;
+/// println!("{}", preview.content.body());
+/// client.send_server_notice(preview).await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct NoticeTemplate {
+    pub default_locale: String,
+    /// Templates keyed by locale, such as `en` or `pt-BR`.
+    pub translations: HashMap<String, String>,
+}
+
+impl NoticeTemplate {
+    pub fn new(default_locale: impl Into<String>, template: impl Into<String>) -> Self {
+        let default_locale = default_locale.into();
+        Self {
+            translations: HashMap::from([(default_locale.clone(), template.into())]),
+            default_locale,
+        }
+    }
+
+    pub fn translation(mut self, locale: impl Into<String>, template: impl Into<String>) -> Self {
+        self.translations.insert(locale.into(), template.into());
+        self
+    }
+
+    /// Pick the template for `locale`, falling back to its language (`pt` for `pt-BR`) and
+    /// then to the default locale.
+    pub fn for_locale(&self, locale: Option<&str>) -> &str {
+        locale
+            .and_then(|locale| {
+                self.translations.get(locale).or_else(|| {
+                    let language = locale.split(['-', '_']).next()?;
+                    self.translations.get(language)
+                })
+            })
+            .or_else(|| self.translations.get(&self.default_locale))
+            .map(String::as_str)
+            .unwrap_or_default()
+    }
+
+    /// Fill in the template for `locale` with `vars`. The values of `vars` are escaped so they
+    /// show up literally, rather than as markdown or HTML, since they may come from users.
+    pub fn render(&self, locale: Option<&str>, vars: &NoticeVariables) -> Result<String> {
+        self.render_with(locale, vars, escape_markdown)
+    }
+
+    /// Fill in the template for `locale` with `vars` without escaping them, for the plain text
+    /// body of the notice.
+    pub fn render_plain(&self, locale: Option<&str>, vars: &NoticeVariables) -> Result<String> {
+        self.render_with(locale, vars, str::to_owned)
+    }
+
+    fn render_with(
+        &self,
+        locale: Option<&str>,
+        vars: &NoticeVariables,
+        escape: impl Fn(&str) -> String,
+    ) -> Result<String> {
+        let template = self.for_locale(locale);
+        let mut rendered = String::with_capacity(template.len());
+        for segment in segments(template) {
+            match segment {
+                Segment::Text(text) => rendered.push_str(text),
+                Segment::Variable(name) => {
+                    let value = vars
+                        .get(name)
+                        .ok_or_else(|| SynapseError::UnknownTemplateVariable(name.to_owned()))?;
+                    rendered.push_str(&escape(value));
+                }
+            }
+        }
+        Ok(rendered)
+    }
+
+    /// The names of the variables used by the template for `locale`.
+    pub fn variables(&self, locale: Option<&str>) -> impl Iterator<Item = &str> {
+        segments(self.for_locale(locale)).filter_map(|segment| match segment {
+            Segment::Variable(name) => Some(name),
+            Segment::Text(_) => None,
+        })
+    }
+}
+
+enum Segment<'a> {
+    Text(&'a str),
+    Variable(&'a str),
+}
+
+/// Split a template into literal text and `{variable}` placeholders. Braces which aren't part
+/// of a placeholder or a `{{`/`}}` escape are kept as text.
+fn segments(template: &str) -> impl Iterator<Item = Segment<'_>> {
+    let mut rest = template;
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        let (segment, len) = match rest.find(['{', '}']) {
+            Some(0) if rest.starts_with("{{") || rest.starts_with("}}") => {
+                (Segment::Text(&rest[..1]), 2)
+            }
+            Some(0) => match rest.strip_prefix('{').and_then(|tail| tail.find('}')) {
+                Some(end) => (Segment::Variable(&rest[1..=end]), end + 2),
+                None => (Segment::Text(&rest[..1]), 1),
+            },
+            Some(i) => (Segment::Text(&rest[..i]), i),
+            None => (Segment::Text(rest), rest.len()),
+        };
+        rest = &rest[len..];
+        Some(segment)
+    })
+}
+
+/// Backslash-escape every ASCII punctuation character, which CommonMark then shows literally.
+/// This covers markdown syntax as well as the `<` and `&` of inline HTML.
+fn escape_markdown(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c.is_ascii_punctuation() {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct NoticeVariables(HashMap<String, String>);
+
+impl NoticeVariables {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.0.insert(name.into(), value.into());
+        self
+    }
+
+    /// Set `name` to `ts` formatted as a `YYYY-MM-DD` date in UTC.
+    pub fn date(self, name: impl Into<String>, ts: SystemTime) -> Self {
+        self.set(name, format_date(ts))
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+}
+
+impl SynapseClient {
+    /// Render the notice `user_id` would receive without sending it.
+    ///
+    /// `locale` looks up the user's preferred locale. If the template uses `displayname`, it is
+    /// filled in from the user's account unless it is already set in `vars`.
+    pub async fn render_templated_notice<'a>(
+        &self,
+        user_id: &'a UserId,
+        template: &NoticeTemplate,
+        vars: &NoticeVariables,
+        locale: impl Fn(&UserId) -> Option<String>,
+    ) -> Result<RichNotice<'a>> {
+        let locale = locale(user_id);
+        let mut vars = vars.clone();
+        let uses_displayname = template
+            .variables(locale.as_deref())
+            .any(|name| name == "displayname");
+        if uses_displayname && vars.get("displayname").is_none() {
+            let user = self.get_user(user_id).await?;
+            let displayname = user.displayname.unwrap_or_else(|| user_id.to_string());
+            vars = vars.set("displayname", displayname);
+        }
+
+        let body = template.render_plain(locale.as_deref(), &vars)?;
+        let markdown = template.render(locale.as_deref(), &vars)?;
+        Ok(match FormattedBody::markdown(markdown) {
+            Some(html) => RichNotice::html(user_id, body, html.body),
+            None => RichNotice::plain(user_id, body),
+        })
+    }
+
+    /// Render a templated notice with `render_templated_notice` and send it.
+    pub async fn send_templated_notice(
+        &self,
+        user_id: &UserId,
+        template: &NoticeTemplate,
+        vars: &NoticeVariables,
+        locale: impl Fn(&UserId) -> Option<String>,
+    ) -> Result<OwnedEventId> {
+        let notice = self
+            .render_templated_notice(user_id, template, vars, locale)
+            .await?;
+        self.send_server_notice(notice).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(template: &str, vars: &NoticeVariables) -> Result<String> {
+        NoticeTemplate::new("en", template).render(None, vars)
+    }
+
+    #[test]
+    fn fills_in_variables() {
+        let vars = NoticeVariables::new().set("name", "Alice");
+        assert_eq!(render("Hi {name}!", &vars).unwrap(), "Hi Alice!");
+    }
+
+    #[test]
+    fn unescapes_double_braces() {
+        let vars = NoticeVariables::new().set("name", "Alice");
+        assert_eq!(
+            render("{{name}} is {name}, }}{{", &vars).unwrap(),
+            "{name} is Alice, }{"
+        );
+    }
+
+    #[test]
+    fn escapes_values() {
+        let vars = NoticeVariables::new().set("displayname", "[renew](https://evil) <img src=x>");
+        let template = NoticeTemplate::new("en", "Hi **{displayname}**");
+
+        let markdown = template.render(None, &vars).unwrap();
+        let html = FormattedBody::markdown(&markdown).unwrap().body;
+        assert!(!html.contains("<a"), "{html}");
+        assert!(!html.contains("<img"), "{html}");
+        assert!(
+            html.contains("[renew](https://evil) &lt;img src=x&gt;"),
+            "{html}"
+        );
+
+        assert_eq!(
+            template.render_plain(None, &vars).unwrap(),
+            "Hi **[renew](https://evil) <img src=x>**"
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_variables() {
+        let err = render("Hi {name}", &NoticeVariables::new()).unwrap_err();
+        assert!(matches!(err, SynapseError::UnknownTemplateVariable(name) if name == "name"));
+    }
+
+    #[test]
+    fn keeps_stray_braces() {
+        let vars = NoticeVariables::new();
+        assert_eq!(render("Hi {name", &vars).unwrap(), "Hi {name");
+        assert_eq!(render("a } b {", &vars).unwrap(), "a } b {");
+    }
+
+    #[test]
+    fn lists_variables_of_the_locale() {
+        let template = NoticeTemplate::new("en", "Hi {displayname}, {{not_a_var}}")
+            .translation("de", "Hallo!");
+        let variables: Vec<_> = template.variables(Some("en")).collect();
+        assert_eq!(variables, ["displayname"]);
+        assert_eq!(template.variables(Some("de-AT")).count(), 0);
+    }
+
+    #[test]
+    fn falls_back_to_language_and_default_locale() {
+        let template = NoticeTemplate::new("en", "Hi").translation("pt", "Olá");
+        assert_eq!(template.for_locale(Some("pt-BR")), "Olá");
+        assert_eq!(template.for_locale(Some("fr")), "Hi");
+        assert_eq!(template.for_locale(None), "Hi");
+    }
+}
@@ -2,4 +2,6 @@ mod user_media;
 pub use user_media::*;
 
 mod room_size;
-pub use room_size::*;
+
+mod analytics;
+pub use analytics::*;
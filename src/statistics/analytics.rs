@@ -0,0 +1,257 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+    iter::Sum,
+};
+
+use futures_util::TryStreamExt;
+use matrix_sdk::ruma::OwnedServerName;
+
+use crate::prelude::*;
+
+use super::{MediaStatisticsQuery, UserMediaStatistics};
+
+/// The number and total size of a set of media.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MediaUsage {
+    pub media_count: usize,
+    /// The total size of the media in bytes.
+    pub media_length: usize,
+}
+
+impl MediaUsage {
+    pub fn record(&mut self, media_count: usize, media_length: usize) {
+        self.media_count += media_count;
+        self.media_length += media_length;
+    }
+}
+
+impl Sum for MediaUsage {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |mut total, usage| {
+            total.record(usage.media_count, usage.media_length);
+            total
+        })
+    }
+}
+
+impl From<&UserMediaStatistics> for MediaUsage {
+    fn from(user: &UserMediaStatistics) -> Self {
+        Self {
+            media_count: user.media_count,
+            media_length: user.media_length,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MediaUsageReport {
+    pub users: Vec<UserMediaStatistics>,
+    /// Usage summed over the users of each homeserver.
+    pub domains: BTreeMap<OwnedServerName, MediaUsage>,
+}
+
+impl MediaUsageReport {
+    pub fn new(users: Vec<UserMediaStatistics>) -> Self {
+        let mut domains = BTreeMap::<OwnedServerName, MediaUsage>::new();
+        for user in &users {
+            domains
+                .entry(user.user_id.server_name().to_owned())
+                .or_default()
+                .record(user.media_count, user.media_length);
+        }
+        Self { users, domains }
+    }
+
+    pub fn total(&self) -> MediaUsage {
+        self.domains.values().copied().sum()
+    }
+
+    /// The `n` users who uploaded the most media items.
+    pub fn top_by_count(&self, n: usize) -> Vec<&UserMediaStatistics> {
+        self.top_by(n, |user| user.media_count)
+    }
+
+    /// The `n` users who uploaded the most bytes of media.
+    pub fn top_by_length(&self, n: usize) -> Vec<&UserMediaStatistics> {
+        self.top_by(n, |user| user.media_length)
+    }
+
+    fn top_by(
+        &self,
+        n: usize,
+        key: impl Fn(&UserMediaStatistics) -> usize,
+    ) -> Vec<&UserMediaStatistics> {
+        let mut users: Vec<_> = self.users.iter().collect();
+        users.sort_by_key(|user| std::cmp::Reverse(key(user)));
+        users.truncate(n);
+        users
+    }
+
+    /// One row per user: `user_id,displayname,media_count,media_length`.
+    pub fn users_csv(&self) -> String {
+        let mut csv = String::from("user_id,displayname,media_count,media_length\n");
+        for user in &self.users {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{}",
+                csv_field(user.user_id.as_str()),
+                csv_field(user.displayname.as_deref().unwrap_or_default()),
+                user.media_count,
+                user.media_length
+            );
+        }
+        csv
+    }
+
+    /// One row per homeserver: `domain,media_count,media_length`.
+    pub fn domains_csv(&self) -> String {
+        let mut csv = String::from("domain,media_count,media_length\n");
+        for (domain, usage) in &self.domains {
+            let _ = writeln!(
+                csv,
+                "{},{},{}",
+                csv_field(domain.as_str()),
+                usage.media_count,
+                usage.media_length
+            );
+        }
+        csv
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UserMediaGrowth {
+    pub user_id: OwnedUserId,
+    pub previous: MediaUsage,
+    pub current: MediaUsage,
+}
+
+impl UserMediaGrowth {
+    /// The change in bytes uploaded between the two windows.
+    pub fn length_delta(&self) -> i128 {
+        self.current.media_length as i128 - self.previous.media_length as i128
+    }
+
+    /// How many times more bytes were uploaded in the current window than the previous one,
+    /// or `None` if nothing was uploaded in the previous window.
+    pub fn length_ratio(&self) -> Option<f64> {
+        (self.previous.media_length > 0)
+            .then(|| self.current.media_length as f64 / self.previous.media_length as f64)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MediaGrowthReport {
+    /// Sorted by `length_delta`, largest growth first.
+    pub users: Vec<UserMediaGrowth>,
+}
+
+impl MediaGrowthReport {
+    /// Users whose uploads grew by more than `factor` times, or who uploaded nothing in the
+    /// previous window and more than `min_new_length` bytes in the current one.
+    pub fn outliers(
+        &self,
+        factor: f64,
+        min_new_length: usize,
+    ) -> impl Iterator<Item = &UserMediaGrowth> {
+        self.users
+            .iter()
+            .filter(move |growth| match growth.length_ratio() {
+                Some(ratio) => ratio > factor,
+                None => growth.current.media_length > min_new_length,
+            })
+    }
+
+    /// One row per user: `user_id,previous_count,previous_length,current_count,current_length`.
+    pub fn to_csv(&self) -> String {
+        let mut csv =
+            String::from("user_id,previous_count,previous_length,current_count,current_length\n");
+        for growth in &self.users {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{}",
+                csv_field(growth.user_id.as_str()),
+                growth.previous.media_count,
+                growth.previous.media_length,
+                growth.current.media_count,
+                growth.current.media_length
+            );
+        }
+        csv
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+impl SynapseClient {
+    /// Collect the media statistics of every user into a report, optionally only counting
+    /// media uploaded within `window`.
+    ///
+    /// ```rs
+    /// let report = client.get_media_usage_report(None).await?;
+    /// for user in report.top_by_length(10) {
+    ///     println!("{}: {} bytes", user.user_id, user.media_length);
+    /// }
+    /// std::fs::write("domains.csv", report.domains_csv())?;
+    /// std::fs::write("report.json", serde_json::to_string(&report)?)?;
+    /// ```
+    pub async fn get_media_usage_report(
+        &self,
+        window: Option<TimeWindow>,
+    ) -> Result<MediaUsageReport> {
+        let users = self.collect_media_statistics(window).await?;
+        Ok(MediaUsageReport::new(users))
+    }
+
+    /// Compare how much media each user uploaded in two time windows.
+    pub async fn get_media_growth_report(
+        &self,
+        previous: TimeWindow,
+        current: TimeWindow,
+    ) -> Result<MediaGrowthReport> {
+        let mut growth = HashMap::<OwnedUserId, UserMediaGrowth>::new();
+        for user in self.collect_media_statistics(Some(previous)).await? {
+            growth.insert(
+                user.user_id.clone(),
+                UserMediaGrowth {
+                    previous: MediaUsage::from(&user),
+                    current: MediaUsage::default(),
+                    user_id: user.user_id,
+                },
+            );
+        }
+        for user in self.collect_media_statistics(Some(current)).await? {
+            growth
+                .entry(user.user_id.clone())
+                .or_insert_with(|| UserMediaGrowth {
+                    user_id: user.user_id.clone(),
+                    previous: MediaUsage::default(),
+                    current: MediaUsage::default(),
+                })
+                .current = MediaUsage::from(&user);
+        }
+
+        let mut users: Vec<_> = growth.into_values().collect();
+        users.sort_by_key(|growth| std::cmp::Reverse(growth.length_delta()));
+        Ok(MediaGrowthReport { users })
+    }
+
+    async fn collect_media_statistics(
+        &self,
+        window: Option<TimeWindow>,
+    ) -> Result<Vec<UserMediaStatistics>> {
+        let query = MediaStatisticsQuery {
+            from_ts: window.map(|window| window.from),
+            until_ts: window.map(|window| window.until),
+            ..MediaStatisticsQuery::builder().build()
+        };
+        self.stream_media_statistics(query).try_collect().await
+    }
+}
@@ -1,4 +1,6 @@
-use crate::prelude::*;
+use futures_util::Stream;
+
+use crate::{pagination::paginate, prelude::*};
 
 #[serde_as]
 #[derive(Debug, Clone, Serialize, TypedBuilder)]
@@ -33,13 +35,13 @@ pub enum MediaOrderBy {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MediaStatistics {
     pub users: Vec<UserMediaStatistics>,
-    pub next_token: Option<String>,
+    pub next_token: Option<usize>,
     pub total: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserMediaStatistics {
-    pub displayname: String,
+    pub displayname: Option<String>,
     pub media_count: usize,
     pub media_length: usize,
     pub user_id: OwnedUserId,
@@ -60,4 +62,20 @@ impl SynapseClient {
                 .await?
         )
     }
+
+    /// Stream the statistics of every user matching `query`, following `next_token` until the
+    /// last page.
+    pub fn stream_media_statistics<'a>(
+        &'a self,
+        query: MediaStatisticsQuery<'a>,
+    ) -> impl Stream<Item = Result<UserMediaStatistics>> + 'a {
+        paginate(query, move |query: MediaStatisticsQuery| async move {
+            let page = self.get_media_statistics(&query).await?;
+            let next = page.next_token.map(|from| MediaStatisticsQuery {
+                from: Some(from),
+                ..query
+            });
+            Ok((page.users, next))
+        })
+    }
 }
@@ -0,0 +1,24 @@
+mod get;
+
+mod list;
+pub use list::*;
+
+mod media;
+pub use media::*;
+
+mod shadow_ban;
+
+mod deactivate;
+
+mod ratelimit;
+pub use ratelimit::*;
+
+mod login;
+
+mod whois;
+pub use whois::*;
+
+mod rooms;
+pub use rooms::*;
+
+mod whoami;
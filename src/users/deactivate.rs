@@ -0,0 +1,22 @@
+use crate::prelude::*;
+
+impl SynapseClient {
+    /// Deactivate a local user, logging them out and removing them from all rooms. If `erase`
+    /// is `true`, their messages are also hidden from users who join rooms later.
+    pub async fn deactivate_user(&self, user_id: &UserId, erase: bool) -> Result<()> {
+        #[derive(Serialize)]
+        struct Body {
+            erase: bool,
+        }
+        execute!(
+            self.inner
+                .post(endpoint!(self format!("/deactivate/{user_id}")))
+                .json(&Body { erase })
+                .send()
+                .await?
+                .json::<MatrixResult<EmptyObject>>()
+                .await?;
+            _r => ()
+        )
+    }
+}
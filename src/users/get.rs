@@ -0,0 +1,16 @@
+use crate::prelude::*;
+
+use super::User;
+
+impl SynapseClient {
+    pub async fn get_user(&self, user_id: &UserId) -> Result<User> {
+        execute!(
+            self.inner
+                .get(endpoint!(v2 self format!("/users/{user_id}")))
+                .send()
+                .await?
+                .json::<MatrixResult<User>>()
+                .await?
+        )
+    }
+}
@@ -0,0 +1,120 @@
+use futures_util::Stream;
+use serde::{Deserialize, Deserializer};
+use serde_with::DisplayFromStr;
+
+use crate::{pagination::paginate, prelude::*};
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Default, Serialize, TypedBuilder)]
+pub struct GetUsersQuery {
+    #[builder(default, setter(strip_option))]
+    pub from: Option<usize>,
+    #[builder(default, setter(strip_option))]
+    pub limit: Option<usize>,
+    /// Only return users whose ID contains this string.
+    #[builder(default, setter(strip_option))]
+    pub user_id: Option<String>,
+    /// Only return users whose ID or display name contains this string.
+    #[builder(default, setter(strip_option))]
+    pub name: Option<String>,
+    /// Whether to include guest users. Synapse includes them by default.
+    #[builder(default, setter(strip_option))]
+    pub guests: Option<bool>,
+    /// Whether to include deactivated users. Synapse excludes them by default.
+    #[builder(default, setter(strip_option))]
+    pub deactivated: Option<bool>,
+    #[builder(default, setter(strip_option))]
+    pub order_by: Option<OrderUsersBy>,
+    #[serde(rename = "dir")]
+    #[builder(default, setter(strip_option))]
+    pub direction: Option<SortDirection>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderUsersBy {
+    Name,
+    IsGuest,
+    Admin,
+    UserType,
+    Deactivated,
+    ShadowBanned,
+    Displayname,
+    AvatarUrl,
+    CreationTs,
+    LastSeenTs,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsersQueryResponse {
+    pub users: Vec<User>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    pub next_token: Option<usize>,
+    pub total: usize,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct User {
+    /// The user's ID.
+    pub name: OwnedUserId,
+    #[serde(deserialize_with = "bool_or_int")]
+    pub is_guest: bool,
+    #[serde(deserialize_with = "bool_or_int")]
+    pub admin: bool,
+    pub user_type: Option<String>,
+    #[serde(deserialize_with = "bool_or_int")]
+    pub deactivated: bool,
+    #[serde(default)]
+    pub shadow_banned: bool,
+    pub displayname: Option<String>,
+    pub avatar_url: Option<OwnedMxcUri>,
+    #[serde_as(as = "Option<TimestampMilliSeconds<i64>>")]
+    #[serde(default)]
+    pub creation_ts: Option<SystemTime>,
+    #[serde_as(as = "Option<TimestampMilliSeconds<i64>>")]
+    #[serde(default)]
+    pub last_seen_ts: Option<SystemTime>,
+}
+
+/// Older Synapse versions return some flags as `0`/`1` rather than booleans.
+fn bool_or_int<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<bool, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum BoolOrInt {
+        Bool(bool),
+        Int(u8),
+    }
+    Ok(match BoolOrInt::deserialize(deserializer)? {
+        BoolOrInt::Bool(b) => b,
+        BoolOrInt::Int(i) => i != 0,
+    })
+}
+
+impl SynapseClient {
+    pub async fn get_users(&self, query: &GetUsersQuery) -> Result<UsersQueryResponse> {
+        execute!(
+            self.inner
+                .get(endpoint!(v2 self "/users"))
+                .query(query)
+                .send()
+                .await?
+                .json::<MatrixResult<UsersQueryResponse>>()
+                .await?
+        )
+    }
+
+    /// Stream every user matching `query`, following `next_token` until the last page.
+    pub fn stream_users(&self, query: GetUsersQuery) -> impl Stream<Item = Result<User>> + '_ {
+        paginate(query, move |query: GetUsersQuery| async move {
+            let page = self.get_users(&query).await?;
+            let next = page.next_token.map(|from| GetUsersQuery {
+                from: Some(from),
+                ..query
+            });
+            Ok((page.users, next))
+        })
+    }
+}
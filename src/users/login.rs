@@ -0,0 +1,38 @@
+use crate::prelude::*;
+
+impl SynapseClient {
+    /// Get an access token which acts as a local user, for investigating problems from their
+    /// point of view. The token doesn't create a device and isn't shown in the user's sessions.
+    ///
+    /// The token is valid until `valid_until`, or until it is logged out if `None`. Server
+    /// admins can't be logged in as.
+    pub async fn login_as_user(
+        &self,
+        user_id: &UserId,
+        valid_until: Option<SystemTime>,
+    ) -> Result<String> {
+        #[serde_as]
+        #[skip_serializing_none]
+        #[derive(Serialize)]
+        struct RequestBody {
+            #[serde_as(as = "Option<TimestampMilliSeconds<i64>>")]
+            valid_until_ms: Option<SystemTime>,
+        }
+        #[derive(Deserialize)]
+        struct Response {
+            access_token: String,
+        }
+        execute!(
+            self.inner
+                .post(endpoint!(self format!("/users/{user_id}/login")))
+                .json(&RequestBody {
+                    valid_until_ms: valid_until,
+                })
+                .send()
+                .await?
+                .json::<MatrixResult<Response>>()
+                .await?;
+            res => res.access_token
+        )
+    }
+}
@@ -0,0 +1,95 @@
+use futures_util::Stream;
+
+use crate::{
+    media::{MediaDeletionResponse, MediaInfo},
+    pagination::paginate,
+    prelude::*,
+};
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, Default, Serialize, TypedBuilder)]
+pub struct UserMediaQuery {
+    #[builder(default, setter(strip_option))]
+    pub limit: Option<usize>,
+    #[builder(default, setter(strip_option))]
+    pub from: Option<usize>,
+    #[builder(default, setter(strip_option))]
+    pub order_by: Option<UserMediaOrderBy>,
+    #[serde(rename = "dir")]
+    #[builder(default, setter(strip_option))]
+    pub direction: Option<SortDirection>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UserMediaOrderBy {
+    MediaId,
+    UploadName,
+    CreatedTs,
+    LastAccessTs,
+    MediaLength,
+    MediaType,
+    QuarantinedBy,
+    SafeFromQuarantine,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserMedia {
+    pub media: Vec<MediaInfo>,
+    pub next_token: Option<usize>,
+    pub total: usize,
+}
+
+impl SynapseClient {
+    /// List the media uploaded by a local user.
+    pub async fn get_user_media(
+        &self,
+        user_id: &UserId,
+        query: &UserMediaQuery,
+    ) -> Result<UserMedia> {
+        execute!(
+            self.inner
+                .get(endpoint!(self format!("/users/{user_id}/media")))
+                .query(query)
+                .send()
+                .await?
+                .json::<MatrixResult<UserMedia>>()
+                .await?
+        )
+    }
+
+    /// Stream all of the media uploaded by a local user, following `next_token` until the last
+    /// page.
+    pub fn stream_user_media<'a>(
+        &'a self,
+        user_id: &'a UserId,
+        query: UserMediaQuery,
+    ) -> impl Stream<Item = Result<MediaInfo>> + 'a {
+        paginate(query, move |query: UserMediaQuery| async move {
+            let page = self.get_user_media(user_id, &query).await?;
+            let next = page.next_token.map(|from| UserMediaQuery {
+                from: Some(from),
+                ..query
+            });
+            Ok((page.media, next))
+        })
+    }
+
+    /// Delete the media uploaded by a local user. `query` selects which page of the user's
+    /// media is deleted, in the same way as for `get_user_media`.
+    pub async fn delete_user_media(
+        &self,
+        user_id: &UserId,
+        query: &UserMediaQuery,
+    ) -> Result<MediaDeletionResponse> {
+        execute!(
+            self.inner
+                .delete(endpoint!(self format!("/users/{user_id}/media")))
+                .query(query)
+                .send()
+                .await?
+                .json::<MatrixResult<MediaDeletionResponse>>()
+                .await?
+        )
+    }
+}
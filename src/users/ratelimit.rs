@@ -0,0 +1,72 @@
+use crate::prelude::*;
+
+/// Overrides the rate limit of a user's messages. A value of `0` disables the limit.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TypedBuilder)]
+#[serde(deny_unknown_fields)]
+pub struct RatelimitOverride {
+    /// Unset values are treated as `0` by Synapse.
+    #[builder(default, setter(strip_option))]
+    pub messages_per_second: Option<u32>,
+    #[builder(default, setter(strip_option))]
+    pub burst_count: Option<u32>,
+}
+
+impl SynapseClient {
+    /// Get the rate limit override of a local user, or `None` if they use the server's
+    /// default rate limit.
+    pub async fn get_ratelimit_override(
+        &self,
+        user_id: &UserId,
+    ) -> Result<Option<RatelimitOverride>> {
+        execute!(
+            self.inner
+                .get(endpoint!(self format!("/users/{user_id}/override_ratelimit")))
+                .send()
+                .await?
+                .json::<MatrixResult<RatelimitOverride>>()
+                .await?;
+            // synapse responds with `{}` when there's no override
+            res => (res != RatelimitOverride::default()).then_some(res)
+        )
+    }
+
+    /// Override the rate limit of a local user.
+    ///
+    /// ```rs
+    /// // let a bridge bot send messages without limits
+    /// let unlimited = RatelimitOverride::builder()
+    ///     .messages_per_second(0)
+    ///     .burst_count(0)
+    ///     .build();
+    /// client.set_ratelimit_override(&bot_id, &unlimited).await?;
+    /// ```
+    pub async fn set_ratelimit_override(
+        &self,
+        user_id: &UserId,
+        ratelimit: &RatelimitOverride,
+    ) -> Result<RatelimitOverride> {
+        execute!(
+            self.inner
+                .post(endpoint!(self format!("/users/{user_id}/override_ratelimit")))
+                .json(ratelimit)
+                .send()
+                .await?
+                .json::<MatrixResult<RatelimitOverride>>()
+                .await?
+        )
+    }
+
+    /// Remove the rate limit override of a local user, restoring the server's default.
+    pub async fn delete_ratelimit_override(&self, user_id: &UserId) -> Result<()> {
+        execute!(
+            self.inner
+                .delete(endpoint!(self format!("/users/{user_id}/override_ratelimit")))
+                .send()
+                .await?
+                .json::<MatrixResult<EmptyObject>>()
+                .await?;
+            _r => ()
+        )
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::BTreeMap;
+
+use matrix_sdk::ruma::{
+    events::{
+        room::{member::MembershipState, power_levels::PowerLevelAction},
+        AnyStateEvent, StateEventType,
+    },
+    Int,
+};
+
+use crate::prelude::*;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserJoinedRooms {
+    pub joined_rooms: Vec<OwnedRoomId>,
+    pub total: usize,
+}
+
+/// A room a user is joined to, with the details needed to list a user's rooms.
+#[derive(Debug, Clone)]
+pub struct UserRoom {
+    pub name: Option<String>,
+    pub canonical_alias: Option<String>,
+    pub joined_members: usize,
+    /// The user's power level, or `None` if the room has no power levels.
+    pub power_level: Option<Int>,
+    /// Whether the user can change the room's power levels.
+    pub is_admin: bool,
+}
+
+#[derive(Debug)]
+pub struct UserRoomOutcome {
+    pub room_id: OwnedRoomId,
+    /// The error if the room couldn't be read, e.g. because it is being deleted.
+    pub result: Result<UserRoom>,
+}
+
+impl SynapseClient {
+    /// List the rooms a user is joined to. Remote users are only known to be in rooms shared
+    /// with local users.
+    pub async fn get_user_joined_rooms(&self, user_id: &UserId) -> Result<UserJoinedRooms> {
+        execute!(
+            self.inner
+                .get(endpoint!(self format!("/users/{user_id}/joined_rooms")))
+                .send()
+                .await?
+                .json::<MatrixResult<UserJoinedRooms>>()
+                .await?
+        )
+    }
+
+    /// Get a user's membership in every room they've been joined to, invited to, knocked on or
+    /// banned from, including rooms they've left.
+    pub async fn get_user_memberships(
+        &self,
+        user_id: &UserId,
+    ) -> Result<BTreeMap<OwnedRoomId, MembershipState>> {
+        #[derive(Deserialize)]
+        struct Response {
+            memberships: BTreeMap<OwnedRoomId, MembershipState>,
+        }
+        execute!(
+            self.inner
+                .get(endpoint!(self format!("/users/{user_id}/memberships")))
+                .send()
+                .await?
+                .json::<MatrixResult<Response>>()
+                .await?;
+            res => res.memberships
+        )
+    }
+
+    /// List the rooms a user is joined to along with each room's name, member count and the
+    /// user's power level. This is the reverse of `get_room_members`.
+    ///
+    /// ```rs
+    /// for room in client.get_user_rooms(&user_id).await? {
+    ///     match room.result {
+    ///         Ok(details) => {
+    ///             let name = details.name.as_deref().unwrap_or(room.room_id.as_str());
+    ///             let role = if details.is_admin { "admin" } else { "member" };
+    ///             println!("{name}\t{}\t{role}", details.joined_members);
+    ///         }
+    ///         Err(err) => eprintln!("{}: {err}", room.room_id),
+    ///     }
+    /// }
+    /// ```
+    pub async fn get_user_rooms(&self, user_id: &UserId) -> Result<Vec<UserRoomOutcome>> {
+        let joined = self.get_user_joined_rooms(user_id).await?;
+        let mut rooms = Vec::with_capacity(joined.joined_rooms.len());
+        for room_id in joined.joined_rooms {
+            let result = self.get_user_room(&room_id, user_id).await;
+            rooms.push(UserRoomOutcome { room_id, result });
+        }
+        Ok(rooms)
+    }
+
+    async fn get_user_room(&self, room_id: &RoomId, user_id: &UserId) -> Result<UserRoom> {
+        let details = self.get_room(room_id).await?;
+        let power_levels = self
+            .get_room_state(room_id)
+            .await?
+            .into_iter()
+            .find_map(|event| match event {
+                AnyStateEvent::RoomPowerLevels(event) => Some(event.power_levels()),
+                _ => None,
+            });
+
+        Ok(UserRoom {
+            name: details.name,
+            canonical_alias: details.canonical_alias,
+            joined_members: details.joined_members,
+            power_level: power_levels
+                .as_ref()
+                .map(|power_levels| power_levels.for_user(user_id)),
+            is_admin: power_levels.is_some_and(|power_levels| {
+                power_levels.user_can_do(
+                    user_id,
+                    PowerLevelAction::SendState(StateEventType::RoomPowerLevels),
+                )
+            }),
+        })
+    }
+}
@@ -0,0 +1,30 @@
+use crate::prelude::*;
+
+impl SynapseClient {
+    /// Shadow-ban a local user. Their requests will appear to succeed, but their events won't
+    /// be sent to other users.
+    pub async fn shadow_ban_user(&self, user_id: &UserId) -> Result<()> {
+        execute!(
+            self.inner
+                .post(endpoint!(self format!("/users/{user_id}/shadow_ban")))
+                .send()
+                .await?
+                .json::<MatrixResult<EmptyObject>>()
+                .await?;
+            _r => ()
+        )
+    }
+
+    /// Lift a shadow-ban from a local user.
+    pub async fn remove_shadow_ban(&self, user_id: &UserId) -> Result<()> {
+        execute!(
+            self.inner
+                .delete(endpoint!(self format!("/users/{user_id}/shadow_ban")))
+                .send()
+                .await?
+                .json::<MatrixResult<EmptyObject>>()
+                .await?;
+            _r => ()
+        )
+    }
+}
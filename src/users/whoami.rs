@@ -0,0 +1,20 @@
+use crate::prelude::*;
+
+impl SynapseClient {
+    /// Get the ID of the user who owns this client's access token.
+    pub async fn whoami(&self) -> Result<OwnedUserId> {
+        #[derive(Deserialize)]
+        struct Response {
+            user_id: OwnedUserId,
+        }
+        execute!(
+            self.inner
+                .get(endpoint!(client self "/account/whoami"))
+                .send()
+                .await?
+                .json::<MatrixResult<Response>>()
+                .await?;
+            res => res.user_id
+        )
+    }
+}
@@ -0,0 +1,196 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::prelude::*;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WhoisInfo {
+    pub user_id: OwnedUserId,
+    /// Keyed by device ID. Synapse currently puts every session under the empty device ID.
+    pub devices: BTreeMap<String, WhoisDevice>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WhoisDevice {
+    pub sessions: Vec<WhoisSession>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WhoisSession {
+    pub connections: Vec<WhoisConnection>,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WhoisConnection {
+    pub ip: String,
+    #[serde_as(as = "TimestampMilliSeconds<i64>")]
+    pub last_seen: SystemTime,
+    pub user_agent: String,
+}
+
+/// Every connection of a user from one IP address with one user agent.
+#[derive(Debug, Clone)]
+pub struct ConnectionGroup {
+    pub ip: String,
+    pub user_agent: String,
+    /// The number of sessions which connected from this IP with this user agent.
+    pub connections: usize,
+    pub last_seen: SystemTime,
+    /// Whether no connection from this IP, with any user agent, was last seen before the time
+    /// passed to `WhoisInfo::connection_groups`.
+    pub new_ip: bool,
+}
+
+impl WhoisInfo {
+    pub fn connections(&self) -> impl Iterator<Item = &WhoisConnection> {
+        self.devices
+            .values()
+            .flat_map(|device| &device.sessions)
+            .flat_map(|session| &session.connections)
+    }
+
+    /// Group the user's connections by IP address and user agent, most recently seen first.
+    ///
+    /// Synapse only records when each connection was last seen, so an IP is considered new if
+    /// all of its connections were last seen after `since`. An IP which was used before `since`
+    /// and is still in use by the same session isn't flagged.
+    ///
+    /// ```rs
+    /// let whois = client.whois(&user_id).await?;
+    /// let yesterday = SystemTime::now() - Duration::from_secs(24 * 60 * 60);
+    /// for group in whois.connection_groups(yesterday).iter().filter(|g| g.new_ip) {
+    ///     println!("new IP {} ({})", group.ip, group.user_agent);
+    /// }
+    /// ```
+    pub fn connection_groups(&self, since: SystemTime) -> Vec<ConnectionGroup> {
+        let old_ips = self.ips_seen_before(since);
+        let mut groups = BTreeMap::<(&str, &str), ConnectionGroup>::new();
+        for connection in self.connections() {
+            groups
+                .entry((&connection.ip, &connection.user_agent))
+                .and_modify(|group| {
+                    group.connections += 1;
+                    group.last_seen = group.last_seen.max(connection.last_seen);
+                })
+                .or_insert_with(|| ConnectionGroup {
+                    ip: connection.ip.clone(),
+                    user_agent: connection.user_agent.clone(),
+                    connections: 1,
+                    last_seen: connection.last_seen,
+                    new_ip: !old_ips.contains(connection.ip.as_str()),
+                });
+        }
+
+        let mut groups: Vec<_> = groups.into_values().collect();
+        groups.sort_by_key(|group| std::cmp::Reverse(group.last_seen));
+        groups
+    }
+
+    /// The IPs the user connected from which are new since `since`, see `connection_groups`.
+    pub fn new_ips(&self, since: SystemTime) -> BTreeSet<&str> {
+        let old_ips = self.ips_seen_before(since);
+        self.connections()
+            .map(|connection| connection.ip.as_str())
+            .filter(|ip| !old_ips.contains(ip))
+            .collect()
+    }
+
+    fn ips_seen_before(&self, since: SystemTime) -> BTreeSet<&str> {
+        self.connections()
+            .filter(|connection| connection.last_seen <= since)
+            .map(|connection| connection.ip.as_str())
+            .collect()
+    }
+}
+
+impl SynapseClient {
+    /// Get the sessions of a user and the IP addresses and user agents they connected with.
+    pub async fn whois(&self, user_id: &UserId) -> Result<WhoisInfo> {
+        execute!(
+            self.inner
+                .get(endpoint!(self format!("/whois/{user_id}")))
+                .send()
+                .await?
+                .json::<MatrixResult<WhoisInfo>>()
+                .await?
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn at(secs: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    fn whois() -> WhoisInfo {
+        let session = |ip: &str, user_agent: &str, secs: u64| {
+            serde_json::json!({
+                "connections": [{
+                    "ip": ip,
+                    "last_seen": secs * 1000,
+                    "user_agent": user_agent
+                }]
+            })
+        };
+        serde_json::from_value(serde_json::json!({
+            "user_id": "@alice:example.com",
+            "devices": {
+                "": {
+                    "sessions": [
+                        session("10.0.0.1", "Element", 500),
+                        session("10.0.0.1", "curl", 3000),
+                        session("10.0.0.2", "Element", 1500),
+                        session("10.0.0.2", "Element", 2000),
+                        session("10.0.0.2", "curl", 4000),
+                        session("10.0.0.3", "Element", 1000)
+                    ]
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn groups_by_ip_and_user_agent() {
+        let groups = whois().connection_groups(at(1000));
+        let summary: Vec<_> = groups
+            .iter()
+            .map(|g| {
+                (
+                    g.ip.as_str(),
+                    g.user_agent.as_str(),
+                    g.connections,
+                    g.new_ip,
+                )
+            })
+            .collect();
+        // most recently seen first
+        assert_eq!(
+            summary,
+            [
+                ("10.0.0.2", "curl", 1, true),
+                ("10.0.0.1", "curl", 1, false),
+                ("10.0.0.2", "Element", 2, true),
+                ("10.0.0.3", "Element", 1, false),
+                ("10.0.0.1", "Element", 1, false),
+            ]
+        );
+        assert_eq!(groups[2].last_seen, at(2000));
+    }
+
+    #[test]
+    fn ip_seen_before_and_after_isnt_new() {
+        let whois = whois();
+        assert_eq!(whois.new_ips(at(1000)), BTreeSet::from(["10.0.0.2"]));
+        assert_eq!(
+            whois.new_ips(at(0)),
+            BTreeSet::from(["10.0.0.1", "10.0.0.2", "10.0.0.3"])
+        );
+        assert!(whois.new_ips(at(5000)).is_empty());
+    }
+}